@@ -0,0 +1,141 @@
+// Converts every PNG in assets/ into sprite data for src/assets.rs. Each image
+// gets the most compact format that holds it exactly: a mask if it only has
+// one color, a palette if it has at most 255, and RGB565 otherwise. Pixels
+// that are less than half opaque are transparent.
+
+use std::{collections::BTreeMap, env, fmt::Write, fs, fs::File, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=assets");
+
+    let mut paths: Vec<_> = fs::read_dir("assets")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "png"))
+        .collect();
+    paths.sort();
+
+    let mut code = String::new();
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let name = path
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_uppercase()
+            .replace(['-', ' '], "_");
+        let (width, height, pixels) = decode(&path);
+        writeln!(
+            code,
+            "pub const {}: Sprite = Sprite::new({}, {}, {});",
+            name,
+            width,
+            height,
+            sprite_data(width, &pixels)
+        )
+        .unwrap();
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("assets.rs");
+    fs::write(out, code).unwrap();
+}
+
+// Returns the image as RGB, with None for transparent pixels.
+fn decode(path: &Path) -> (usize, usize, Vec<Option<[u8; 3]>>) {
+    let mut decoder = png::Decoder::new(File::open(path).unwrap());
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().unwrap();
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).unwrap();
+
+    let channels = info.color_type.samples();
+    let pixels = buffer[..info.buffer_size()]
+        .chunks(channels)
+        .map(|pixel| {
+            let (rgb, alpha) = match *pixel {
+                [gray] => ([gray; 3], 255),
+                [gray, alpha] => ([gray; 3], alpha),
+                [r, g, b] => ([r, g, b], 255),
+                [r, g, b, alpha] => ([r, g, b], alpha),
+                _ => unreachable!(),
+            };
+            (alpha >= 128).then_some(rgb)
+        })
+        .collect();
+    (info.width as usize, info.height as usize, pixels)
+}
+
+fn sprite_data(width: usize, pixels: &[Option<[u8; 3]>]) -> String {
+    let mut palette = BTreeMap::new();
+    for rgb in pixels.iter().flatten() {
+        let next = palette.len();
+        palette.entry(*rgb).or_insert(next);
+    }
+    let transparent = pixels.iter().any(Option::is_none);
+    let mask = || bytes(&mask(width, pixels));
+
+    if palette.len() <= 1 {
+        format!("SpriteData::Mask(&{})", mask())
+    } else if palette.len() <= 255 {
+        let mut colors = vec![0; palette.len()];
+        for (rgb, index) in &palette {
+            colors[*index] = u32::from_be_bytes([0, rgb[0], rgb[1], rgb[2]]);
+        }
+        let indices: Vec<_> = pixels
+            .iter()
+            .map(|pixel| pixel.map_or(palette.len(), |rgb| palette[&rgb]) as u8)
+            .collect();
+        format!(
+            "SpriteData::Indexed {{ palette: &[{}], pixels: &{}, transparent: {} }}",
+            colors
+                .iter()
+                .map(|color| format!("Color::from_hex(0x{:06x})", color))
+                .collect::<Vec<_>>()
+                .join(", "),
+            bytes(&indices),
+            if transparent {
+                format!("Some({})", palette.len())
+            } else {
+                "None".into()
+            }
+        )
+    } else {
+        let rgb565: Vec<_> = pixels
+            .iter()
+            .map(|pixel| {
+                let [r, g, b] = pixel.unwrap_or_default().map(u16::from);
+                format!("0x{:04x}", (r >> 3) << 11 | (g >> 2) << 5 | b >> 3)
+            })
+            .collect();
+        format!(
+            "SpriteData::Rgb565 {{ pixels: &[{}], mask: {} }}",
+            rgb565.join(", "),
+            if transparent {
+                format!("Some(&{})", mask())
+            } else {
+                "None".into()
+            }
+        )
+    }
+}
+
+// One bit per pixel, set where it's opaque, in the layout SpriteData::Mask
+// uses.
+fn mask(width: usize, pixels: &[Option<[u8; 3]>]) -> Vec<u8> {
+    pixels
+        .chunks(width)
+        .flat_map(|row| {
+            row.chunks(8).map(|byte| {
+                byte.iter().enumerate().fold(0, |mask, (bit, pixel)| {
+                    mask | (pixel.is_some() as u8) << (7 - bit)
+                })
+            })
+        })
+        .collect()
+}
+
+fn bytes(bytes: &[u8]) -> String {
+    let bytes: Vec<_> = bytes.iter().map(|byte| format!("0x{:02x}", byte)).collect();
+    format!("[{}]", bytes.join(", "))
+}
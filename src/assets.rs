@@ -0,0 +1,9 @@
+// Sprites generated from the PNGs in assets/, one constant per file, named
+// after it. assets/dvd_logo.png becomes DVD_LOGO.
+
+use crate::{
+    display::Color,
+    sprite::{Sprite, SpriteData},
+};
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
@@ -0,0 +1,197 @@
+use super::{
+    frame::Frame,
+    spec::{self, Geometry},
+    Color, ColorCorrection, Dithering, Modulation,
+};
+
+pub const MAX_PLANES: usize = max(spec::COLOR_BITMASK as usize + 1, spec::BCM_BITS);
+
+// Each plane holds one byte per pixel pair, so a block of W * H words fits
+// 8 planes of a W * H canvas. Layouts with gaps need less than that.
+const PLANES_PER_BLOCK: usize = 8;
+const BLOCKS: usize = MAX_PLANES.div_ceil(PLANES_PER_BLOCK);
+
+const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+// A frame converted into the exact bits that get shifted into the panels.
+//
+// Each row of a plane holds one byte per physical column, packed four to a
+// word. The low six bits of each byte are R1, R2, G1, G2, B1, B2, in the same
+// order as the data pins. Planes and rows are laid out back to back in
+// physical order, so the whole thing can be streamed out as one slice.
+#[repr(C)]
+pub struct Bitplanes<
+    const W: usize = { spec::VIRTUAL_WIDTH },
+    const H: usize = { spec::VIRTUAL_HEIGHT },
+> {
+    blocks: [[[u32; W]; H]; BLOCKS],
+    geometry: Geometry,
+    plane_count: usize,
+    modulation: Modulation,
+    brightness: u8,
+}
+
+impl<const W: usize, const H: usize> Bitplanes<W, H> {
+    pub const fn new(geometry: Geometry) -> Self {
+        Self {
+            blocks: [[[0; W]; H]; BLOCKS],
+            geometry,
+            plane_count: spec::COLOR_BITMASK as usize + 1,
+            modulation: Modulation::Threshold,
+            brightness: u8::MAX,
+        }
+    }
+
+    // The phase only matters for temporal dithering, and should go up by one
+    // for each frame.
+    pub fn commit<F: Frame<W, H>>(
+        &mut self,
+        frame: &F,
+        modulation: Modulation,
+        correction: &ColorCorrection,
+        dithering: Dithering,
+        phase: usize,
+    ) {
+        self.modulation = modulation;
+        self.plane_count = match modulation {
+            Modulation::Threshold => spec::COLOR_BITMASK as usize + 1,
+            Modulation::BinaryCoded => spec::BCM_BITS,
+        };
+        let bits = match modulation {
+            Modulation::Threshold => spec::COLOR_BITMASK.count_ones() as usize,
+            Modulation::BinaryCoded => spec::BCM_BITS,
+        };
+        let pixel = |x, y| dithering.apply(correction.apply(frame.pixel(x, y)), x, y, phase, bits);
+
+        let geometry = self.geometry;
+        let (panel_width, panel_height) = (geometry.panel_width, geometry.panel_height);
+        let row_pairs = geometry.row_pairs();
+        let segments = geometry.rows_per_address();
+        let words_per_row = self.words_per_row();
+        let plane_count = self.plane_count;
+        let words = self.words_mut();
+
+        for (panel, placement) in geometry.layout.iter().enumerate() {
+            for y_pair in 0..row_pairs {
+                // Panels that light several rows per address take them one
+                // after another, a panel's width at a time.
+                for segment in 0..segments {
+                    let top_y = geometry.panel_y(y_pair, segment);
+                    let bottom_y = geometry.panel_y(y_pair + row_pairs, segment);
+                    for panel_x in 0..panel_width {
+                        let (top_x, top_y) =
+                            placement.to_virtual(panel_x, top_y, panel_width, panel_height);
+                        let (bottom_x, bottom_y) =
+                            placement.to_virtual(panel_x, bottom_y, panel_width, panel_height);
+                        let (top, bottom) = (pixel(top_x, top_y), pixel(bottom_x, bottom_y));
+
+                        let x = (panel * segments + segment) * panel_width + panel_x;
+                        let shift = 8 * (x % 4);
+
+                        for plane in 0..plane_count {
+                            let column = match modulation {
+                                Modulation::Threshold => {
+                                    threshold_bits(top, plane as u32)
+                                        | threshold_bits(bottom, plane as u32) << 1
+                                }
+                                Modulation::BinaryCoded => {
+                                    let bit = plane + 8 - spec::BCM_BITS;
+                                    plane_bits(top, bit) | plane_bits(bottom, bit) << 1
+                                }
+                            };
+
+                            let word =
+                                &mut words[(plane * row_pairs + y_pair) * words_per_row + x / 4];
+                            *word = *word & !(0xff << shift) | column << shift;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn geometry(&self) -> &Geometry {
+        &self.geometry
+    }
+
+    pub fn modulation(&self) -> Modulation {
+        self.modulation
+    }
+
+    // Fraction of the time each row is lit for, out of 255. Scales the light
+    // output without touching the colors.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
+
+    pub fn plane_count(&self) -> usize {
+        self.plane_count
+    }
+
+    pub fn words_per_row(&self) -> usize {
+        self.geometry.physical_width() / 4
+    }
+
+    // Relative on-time of a plane.
+    pub fn weight(&self, plane: usize) -> u32 {
+        match self.modulation {
+            Modulation::Threshold => 1,
+            Modulation::BinaryCoded => 1 << plane,
+        }
+    }
+
+    pub fn row(&self, plane: usize, y_pair: usize) -> &[u32] {
+        let words_per_row = self.words_per_row();
+        let start = (plane * self.geometry.row_pairs() + y_pair) * words_per_row;
+        &self.words()[start..start + words_per_row]
+    }
+
+    // All planes in use, back to back.
+    pub fn words(&self) -> &[u32] {
+        let len = self.plane_count * self.geometry.row_pairs() * self.words_per_row();
+        // SAFETY: The nested arrays are contiguous, and the planes in use never
+        // take up more than all of them.
+        unsafe { core::slice::from_raw_parts(self.blocks.as_ptr() as *const u32, len) }
+    }
+
+    fn words_mut(&mut self) -> &mut [u32] {
+        let len = self.plane_count * self.geometry.row_pairs() * self.words_per_row();
+        // SAFETY: As above.
+        unsafe { core::slice::from_raw_parts_mut(self.blocks.as_mut_ptr() as *mut u32, len) }
+    }
+}
+
+// Spreads the red, green and blue bits of one pixel out so that they land on
+// the R1, G1 and B1 positions. Shift left by one for R2, G2 and B2.
+fn spread(r: bool, g: bool, b: bool) -> u32 {
+    r as u32 | (g as u32) << 2 | (b as u32) << 4
+}
+
+fn threshold_bits(color: Color, div: u32) -> u32 {
+    const SHIFT: usize = 8 - spec::COLOR_BITMASK.count_ones() as usize;
+    let n = color.hex();
+    spread(
+        div < n >> 16 >> SHIFT,
+        div < (n >> 8 >> SHIFT) & spec::COLOR_BITMASK,
+        div < (n >> SHIFT) & spec::COLOR_BITMASK,
+    )
+}
+
+fn plane_bits(color: Color, bit: usize) -> u32 {
+    let n = color.hex();
+    spread(
+        (n >> 16 >> bit) & 1 > 0,
+        (n >> 8 >> bit) & 1 > 0,
+        (n >> bit) & 1 > 0,
+    )
+}
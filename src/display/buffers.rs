@@ -0,0 +1,52 @@
+use super::{frame::Frame, spec, Matrix};
+
+// A front buffer that's being shown and a back buffer that's being drawn into.
+// Nothing drawn into the back buffer is visible until it's presented.
+pub struct FrameBuffers<
+    const W: usize = { spec::VIRTUAL_WIDTH },
+    const H: usize = { spec::VIRTUAL_HEIGHT },
+    F = Matrix<W, H>,
+> {
+    front: F,
+    back: F,
+    preserve: bool,
+}
+
+impl<const W: usize, const H: usize, F: Frame<W, H>> FrameBuffers<W, H, F> {
+    pub const fn new() -> Self {
+        Self {
+            front: F::BLANK,
+            back: F::BLANK,
+            preserve: true,
+        }
+    }
+
+    pub fn front(&self) -> &F {
+        &self.front
+    }
+
+    pub fn back(&mut self) -> &mut F {
+        &mut self.back
+    }
+
+    // With preserve on (the default), presenting copies the new front buffer
+    // into the back buffer so drawing can pick up where it left off. With it
+    // off, the back buffer is left holding whatever was shown before, which
+    // is cheaper when every frame is drawn from scratch anyway.
+    pub fn set_preserve(&mut self, preserve: bool) {
+        self.preserve = preserve;
+    }
+
+    pub fn swap(&mut self) {
+        core::mem::swap(&mut self.front, &mut self.back);
+        if self.preserve {
+            self.back = self.front;
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, F: Frame<W, H>> Default for FrameBuffers<W, H, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
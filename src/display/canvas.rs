@@ -0,0 +1,130 @@
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Size},
+    pixelcolor::{IntoStorage, Rgb565, Rgb888, RgbColor},
+    primitives::Rectangle,
+    Pixel,
+};
+
+use super::{spec, Color, Matrix, Rgb565Frame};
+
+// Lets embedded-graphics draw into a matrix, e.g. the back buffer handed to a
+// render function. Anything outside the matrix is clipped.
+pub struct Canvas<'a, const W: usize = { spec::VIRTUAL_WIDTH }, const H: usize = { spec::VIRTUAL_HEIGHT }> {
+    matrix: &'a mut Matrix<W, H>,
+}
+
+impl<'a, const W: usize, const H: usize> Canvas<'a, W, H> {
+    pub fn new(matrix: &'a mut Matrix<W, H>) -> Self {
+        Self { matrix }
+    }
+
+    pub fn matrix(&mut self) -> &mut Matrix<W, H> {
+        self.matrix
+    }
+
+    // Does nothing if the pixel is off the edge.
+    #[inline(always)]
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: Color) {
+        if (0..W as i32).contains(&x) && (0..H as i32).contains(&y) {
+            self.matrix[y as usize][x as usize] = color;
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> OriginDimensions for Canvas<'_, W, H> {
+    fn size(&self) -> Size {
+        Size::new(W as u32, H as u32)
+    }
+}
+
+impl<const W: usize, const H: usize> DrawTarget for Canvas<'_, W, H> {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            self.set_pixel(point.x, point.y, color.into());
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let color = Color::from(color);
+        for y in area.rows() {
+            for x in area.columns() {
+                self.matrix[y as usize][x as usize] = color;
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        *self.matrix = [[Color::from(color); W]; H];
+        Ok(())
+    }
+}
+
+// RGB565 frames can be drawn into directly, with no conversion at all.
+impl<const W: usize, const H: usize> OriginDimensions for Rgb565Frame<W, H> {
+    fn size(&self) -> Size {
+        Size::new(W as u32, H as u32)
+    }
+}
+
+impl<const W: usize, const H: usize> DrawTarget for Rgb565Frame<W, H> {
+    type Color = Rgb565;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if (0..W as i32).contains(&point.x) && (0..H as i32).contains(&point.y) {
+                self.pixels[point.y as usize][point.x as usize] = color.into_storage();
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let color = color.into_storage();
+        for y in area.rows() {
+            for x in area.columns() {
+                self.pixels[y as usize][x as usize] = color;
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.pixels = [[color.into_storage(); W]; H];
+        Ok(())
+    }
+}
+
+impl From<Rgb888> for Color {
+    fn from(color: Rgb888) -> Self {
+        Color::from_rgb(color.r(), color.g(), color.b())
+    }
+}
+
+impl From<Color> for Rgb888 {
+    fn from(color: Color) -> Self {
+        Rgb888::new(color.r(), color.g(), color.b())
+    }
+}
+
+impl From<Color> for Rgb565 {
+    fn from(color: Color) -> Self {
+        Rgb565::new(color.r() >> 3, color.g() >> 2, color.b() >> 3)
+    }
+}
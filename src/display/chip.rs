@@ -0,0 +1,57 @@
+use embedded_hal::digital::v2::PinState;
+
+use super::pins::Hub75Pins;
+
+// Configuration registers for FM6126A style column drivers, shifted in MSB
+// first. Register 1 sets the output current and turns the outputs on, register
+// 2 tweaks blanking.
+const FM6126A_REG1: u16 = 0b0111_1111_1111_1111;
+const FM6126A_REG2: u16 = 0b0000_0000_0100_0000;
+
+// The column driver chips on the panels. Most panels work with any plain shift
+// register driver, but some stay dark until they're configured.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, defmt::Format)]
+pub enum PanelChip {
+    // Plain shift registers, e.g. ICN2037 or MBI5124. Nothing to configure.
+    #[default]
+    Generic,
+    Fm6126a,
+    // Takes the same configuration as the FM6126A.
+    Icn2038s,
+}
+
+impl PanelChip {
+    // Writes the chips' configuration registers over the data, CLK and LAT
+    // lines, with the panels blanked. Has to happen before anything is
+    // scanned out.
+    pub fn configure<P: Hub75Pins>(self, pins: &mut P, columns: usize) -> Result<(), P::Error> {
+        // A register is selected by how many clocks LAT is held high for at
+        // the end of the row.
+        let registers = match self {
+            PanelChip::Generic => return Ok(()),
+            PanelChip::Fm6126a | PanelChip::Icn2038s => [(FM6126A_REG1, 11), (FM6126A_REG2, 12)],
+        };
+
+        pins.set_oe(PinState::High)?;
+        pins.set_latch(PinState::Low)?;
+        for (value, latch_clocks) in registers {
+            for x in 0..columns {
+                // Every chip gets a copy, so every data line carries the same bit.
+                let bit = (value >> (15 - x % 16)) & 1;
+                pins.set_data(if bit > 0 { 0b111111 } else { 0 })?;
+                pins.set_latch(if x + latch_clocks >= columns {
+                    PinState::High
+                } else {
+                    PinState::Low
+                })?;
+
+                pins.set_clk(PinState::High)?;
+                pins.delay(1);
+                pins.set_clk(PinState::Low)?;
+                pins.delay(1);
+            }
+            pins.set_latch(PinState::Low)?;
+        }
+        pins.set_data(0)
+    }
+}
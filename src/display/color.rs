@@ -18,7 +18,109 @@ impl Color {
 		Self(0xffffff)
 	}
 
+	// Hue in degrees, saturation and value out of 255.
+	pub const fn from_hsv(hue: u16, saturation: u8, value: u8) -> Self {
+		let (s, v) = (saturation as u32, value as u32);
+		let hue = hue as u32 % 360;
+		let rem = hue % 60 * 255 / 60;
+		let p = (v * (255 - s) / 255) as u8;
+		let q = (v * (255 - s * rem / 255) / 255) as u8;
+		let t = (v * (255 - s * (255 - rem) / 255) / 255) as u8;
+		match hue / 60 {
+			0 => Self::from_rgb(value, t, p),
+			1 => Self::from_rgb(q, value, p),
+			2 => Self::from_rgb(p, value, t),
+			3 => Self::from_rgb(p, q, value),
+			4 => Self::from_rgb(t, p, value),
+			_ => Self::from_rgb(value, p, q),
+		}
+	}
+
+	// Hue in degrees, saturation and lightness out of 255.
+	pub const fn from_hsl(hue: u16, saturation: u8, lightness: u8) -> Self {
+		let (s, l) = (saturation as i32, lightness as i32);
+		let hue = hue as i32 % 360;
+		let chroma = (255 - (2 * l - 255).abs()) * s / 255;
+		let x = chroma * (60 - (hue % 120 - 60).abs()) / 60;
+		let m = l - chroma / 2;
+		let (r, g, b) = match hue / 60 {
+			0 => (chroma, x, 0),
+			1 => (x, chroma, 0),
+			2 => (0, chroma, x),
+			3 => (0, x, chroma),
+			4 => (x, 0, chroma),
+			_ => (chroma, 0, x),
+		};
+		Self::from_rgb((r + m) as u8, (g + m) as u8, (b + m) as u8)
+	}
+
+	// Expands each channel by repeating its top bits, so that converting back
+	// gives the same value.
+	pub const fn from_rgb565(rgb565: u16) -> Self {
+		let r = (rgb565 >> 11) as u8 & 0x1f;
+		let g = (rgb565 >> 5) as u8 & 0x3f;
+		let b = rgb565 as u8 & 0x1f;
+		Self::from_rgb(r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2)
+	}
+
+	pub const fn from_rgb888(bytes: [u8; 3]) -> Self {
+		Self::from_rgb(bytes[0], bytes[1], bytes[2])
+	}
+
 	pub const fn hex(&self) -> u32 {
 		self.0
 	}
+
+	pub const fn r(&self) -> u8 {
+		(self.0 >> 16) as u8
+	}
+
+	pub const fn g(&self) -> u8 {
+		(self.0 >> 8) as u8
+	}
+
+	pub const fn b(&self) -> u8 {
+		self.0 as u8
+	}
+
+	pub const fn to_rgb565(&self) -> u16 {
+		(self.r() as u16 >> 3) << 11 | (self.g() as u16 >> 2) << 5 | self.b() as u16 >> 3
+	}
+
+	pub const fn to_rgb888(&self) -> [u8; 3] {
+		[self.r(), self.g(), self.b()]
+	}
+
+	// Moves t / 255 of the way towards the other color.
+	pub const fn lerp(self, other: Color, t: u8) -> Self {
+		Self::from_rgb(
+			lerp_channel(self.r(), other.r(), t),
+			lerp_channel(self.g(), other.g(), t),
+			lerp_channel(self.b(), other.b(), t),
+		)
+	}
+
+	// Draws the other color on top with an opacity out of 255.
+	pub const fn blend(self, top: Color, alpha: u8) -> Self {
+		self.lerp(top, alpha)
+	}
+
+	// Adds the channels together, like overlapping lights.
+	pub const fn saturating_add(self, other: Color) -> Self {
+		Self::from_rgb(
+			self.r().saturating_add(other.r()),
+			self.g().saturating_add(other.g()),
+			self.b().saturating_add(other.b()),
+		)
+	}
+
+	// Multiplies every channel by factor / 255.
+	pub const fn scale(self, factor: u8) -> Self {
+		Self::black().lerp(self, factor)
+	}
+}
+
+const fn lerp_channel(from: u8, to: u8, t: u8) -> u8 {
+	let (from, to, t) = (from as i32, to as i32, t as i32);
+	(from + ((to - from) * t + 127 * (to - from).signum()) / 255) as u8
 }
\ No newline at end of file
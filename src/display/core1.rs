@@ -0,0 +1,203 @@
+use rp2040_hal::{
+    gpio::{bank0, Pin, PullDownDisabled},
+    multicore::{Error, Multicore, Stack},
+    pac,
+    sio::SioFifo,
+    Sio,
+};
+
+use super::{
+    bitplanes::Bitplanes,
+    frame::Frame,
+    pins::{DefaultPins, Hub75Pins},
+    scanner::Scanner,
+    spec, ColorCorrection, Dithering, Matrix, Modulation, Pipeline,
+};
+
+static mut CORE1_STACK: Stack<1024> = Stack::new();
+
+// Marks a FIFO message as a new brightness in the low byte, rather than the
+// index of the bitplanes to switch to.
+const SET_BRIGHTNESS: u32 = 1 << 8;
+
+// Bitplanes shared between the cores. At any time core 1 is scanning out of
+// one of them and core 0 owns the other; ownership only changes hands over the
+// SIO FIFO.
+struct Shared<const W: usize, const H: usize>(*mut [Bitplanes<W, H>; 2]);
+
+// SAFETY: See above.
+unsafe impl<const W: usize, const H: usize> Send for Shared<W, H> {}
+
+// Core 0's handle on a display that core 1 is refreshing.
+//
+// Presenting converts the frame into bitplanes on core 0 and then sends core 1
+// the index of the buffer to switch to, which it picks up at the next frame
+// boundary and acknowledges. Brightness changes go over the FIFO too, so they
+// don't have to wait for a present. Core 1 never does anything but scan out.
+pub struct Core1Driver<
+    'a,
+    const W: usize = { spec::VIRTUAL_WIDTH },
+    const H: usize = { spec::VIRTUAL_HEIGHT },
+    F = Matrix<W, H>,
+> {
+    pipeline: Pipeline<W, H, F>,
+    bitplanes: Shared<W, H>,
+    brightness: u8,
+    fifo: &'a mut SioFifo,
+    active: usize,
+    pending: bool,
+}
+
+impl<'a, const W: usize, const H: usize, F: Frame<W, H>> Core1Driver<'a, W, H, F> {
+    // Uses the board's own wiring; see DefaultPins.
+    #[allow(clippy::too_many_arguments)]
+    pub fn spawn(
+        bitplanes: &'static mut [Bitplanes<W, H>; 2],
+        psm: &mut pac::PSM,
+        ppb: &mut pac::PPB,
+        fifo: &'a mut SioFifo,
+        r1: Pin<bank0::Gpio2, PullDownDisabled>,
+        r2: Pin<bank0::Gpio3, PullDownDisabled>,
+        g1: Pin<bank0::Gpio4, PullDownDisabled>,
+        g2: Pin<bank0::Gpio5, PullDownDisabled>,
+        b1: Pin<bank0::Gpio6, PullDownDisabled>,
+        b2: Pin<bank0::Gpio7, PullDownDisabled>,
+        clk: Pin<bank0::Gpio8, PullDownDisabled>,
+        latch: Pin<bank0::Gpio9, PullDownDisabled>,
+        a: Pin<bank0::Gpio10, PullDownDisabled>,
+        b: Pin<bank0::Gpio11, PullDownDisabled>,
+        c: Pin<bank0::Gpio12, PullDownDisabled>,
+        d: Pin<bank0::Gpio13, PullDownDisabled>,
+        oe: Pin<bank0::Gpio14, PullDownDisabled>,
+    ) -> Result<Self, Error> {
+        let pins = DefaultPins::new(r1, r2, g1, g2, b1, b2, clk, latch, a, b, c, d, oe);
+        Self::spawn_with_pins(bitplanes, psm, ppb, fifo, pins)
+    }
+
+    // Must only be called once. Panics if the bitplanes' geometry doesn't fit
+    // a W by H canvas.
+    pub fn spawn_with_pins<P: Hub75Pins + Send + 'static>(
+        bitplanes: &'static mut [Bitplanes<W, H>; 2],
+        psm: &mut pac::PSM,
+        ppb: &mut pac::PPB,
+        fifo: &'a mut SioFifo,
+        mut pins: P,
+    ) -> Result<Self, Error> {
+        let geometry = bitplanes[0].geometry();
+        geometry.validate(W, H).unwrap();
+        geometry.chip.configure(&mut pins, geometry.physical_width()).unwrap();
+        let shared = Shared(bitplanes);
+        let core1_shared = Shared(shared.0);
+
+        let mut scanner = Scanner::new(pins);
+
+        let mut multicore = Multicore::new(psm, ppb, fifo);
+        let core1 = &mut multicore.cores()[1];
+        // SAFETY: Core 1 is only ever started from here.
+        let stack = unsafe { &mut *core::ptr::addr_of_mut!(CORE1_STACK.mem) };
+        core1.spawn(stack, move || {
+            // SAFETY: Core 1 only touches the FIFO, which has a separate end
+            // for each core.
+            let pac = unsafe { pac::Peripherals::steal() };
+            let mut fifo = Sio::new(pac.SIO).fifo;
+
+            let shared = core1_shared;
+            let mut active = 0;
+            loop {
+                while let Some(message) = fifo.read() {
+                    if message & SET_BRIGHTNESS != 0 {
+                        // SAFETY: Core 0 doesn't touch the active bitplanes,
+                        // and sets the brightness on the others itself.
+                        unsafe { (*shared.0)[active].set_brightness(message as u8) };
+                    } else {
+                        active = message as usize;
+                        fifo.write(0);
+                    }
+                }
+                // SAFETY: Core 0 doesn't write to the active bitplanes until
+                // core 1 has acknowledged switching away from them.
+                let bitplanes = unsafe { &(*shared.0)[active] };
+                scanner.draw(bitplanes).unwrap();
+            }
+        })?;
+
+        Ok(Self {
+            pipeline: Pipeline::new(),
+            bitplanes: shared,
+            brightness: u8::MAX,
+            fifo,
+            active: 0,
+            pending: false,
+        })
+    }
+
+    // Takes effect from the next present.
+    pub fn set_modulation(&mut self, modulation: Modulation) {
+        self.pipeline.set_modulation(modulation);
+    }
+
+    // Defaults to ColorCorrection::CIE. Takes effect from the next present.
+    pub fn set_color_correction(&mut self, correction: ColorCorrection) {
+        self.pipeline.set_color_correction(correction);
+    }
+
+    // Takes effect from the next present.
+    pub fn set_dithering(&mut self, dithering: Dithering) {
+        self.pipeline.set_dithering(dithering);
+    }
+
+    // 255 is full brightness. Takes effect from the next frame core 1 scans
+    // out, without waiting for a present.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+        self.fifo.write_blocking(SET_BRIGHTNESS | brightness as u32);
+    }
+
+    pub fn set_preserve_back_buffer(&mut self, preserve: bool) {
+        self.pipeline.set_preserve_back_buffer(preserve);
+    }
+
+    pub fn back_buffer(&mut self) -> &mut F {
+        self.pipeline.back_buffer()
+    }
+
+    // Makes the back buffer visible. Blocks only if core 1 hasn't reached the
+    // end of the frame since the previous present.
+    pub fn present(&mut self) {
+        self.pipeline.swap();
+        self.show_front();
+    }
+
+    // Commits the front buffer and hands it to core 1.
+    fn show_front(&mut self) {
+        if self.pending {
+            self.fifo.read_blocking();
+        }
+
+        let next = 1 - self.active;
+        // SAFETY: Core 1 is scanning out of the other buffer, and won't switch
+        // to this one until it's told to below.
+        let bitplanes = unsafe { &mut (*self.bitplanes.0)[next] };
+        self.pipeline.commit(bitplanes);
+        bitplanes.set_brightness(self.brightness);
+        self.pipeline.advance_phase();
+
+        self.fifo.write_blocking(next as u32);
+        self.active = next;
+        self.pending = true;
+    }
+
+    // The render function draws into the back buffer and returns whether it
+    // should be presented. Refresh rate is unaffected by how long it takes.
+    // With temporal dithering, idle iterations recommit the front buffer to
+    // keep the pattern moving.
+    pub fn draw_loop(&mut self, mut render: impl FnMut(&mut F) -> bool) {
+        loop {
+            if render(self.pipeline.back_buffer()) {
+                self.present();
+            } else if self.pipeline.dithering() == Dithering::Temporal {
+                self.show_front();
+            }
+        }
+    }
+}
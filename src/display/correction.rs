@@ -0,0 +1,108 @@
+use super::Color;
+
+// Maps each channel of a color through a lookup table before it's turned into
+// duty cycles. The panels' light output is linear in duty, but perceived
+// brightness isn't, and the red, green and blue LEDs aren't equally bright.
+#[derive(Clone)]
+pub struct ColorCorrection {
+    tables: [[u8; 256]; 3],
+}
+
+impl ColorCorrection {
+    // Leaves colors untouched.
+    pub const LINEAR: Self = Self::from_curve(linear_curve());
+
+    // Inverse of CIE 1976 lightness, so that equal steps in a channel look
+    // like equal steps in brightness.
+    pub const CIE: Self = Self::from_curve(cie_curve());
+
+    // Uses the same curve for every channel.
+    pub const fn from_curve(curve: [u8; 256]) -> Self {
+        Self::from_curves([curve, curve, curve])
+    }
+
+    // Separate red, green and blue curves, for panels whose LEDs don't ramp
+    // up the same way.
+    pub const fn from_curves(curves: [[u8; 256]; 3]) -> Self {
+        Self { tables: curves }
+    }
+
+    pub fn gamma(gamma: f32) -> Self {
+        Self::from_curve(gamma_curve(gamma))
+    }
+
+    pub fn gamma_rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::from_curves([gamma_curve(r), gamma_curve(g), gamma_curve(b)])
+    }
+
+    // Scales each channel's curve by a gain out of 255, e.g. to pull down
+    // green and blue on panels with a cold tint.
+    pub const fn with_white_balance(mut self, r: u8, g: u8, b: u8) -> Self {
+        let gains = [r, g, b];
+        let mut channel = 0;
+        while channel < 3 {
+            let mut i = 0;
+            while i < 256 {
+                let value = self.tables[channel][i] as u32 * gains[channel] as u32;
+                self.tables[channel][i] = ((value + 127) / 255) as u8;
+                i += 1;
+            }
+            channel += 1;
+        }
+        self
+    }
+
+    #[inline(always)]
+    pub fn apply(&self, color: Color) -> Color {
+        let n = color.hex();
+        Color::from_rgb(
+            self.tables[0][(n >> 16) as usize & 0xff],
+            self.tables[1][(n >> 8) as usize & 0xff],
+            self.tables[2][n as usize & 0xff],
+        )
+    }
+}
+
+impl Default for ColorCorrection {
+    fn default() -> Self {
+        Self::CIE
+    }
+}
+
+const fn linear_curve() -> [u8; 256] {
+    let mut curve = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        curve[i] = i as u8;
+        i += 1;
+    }
+    curve
+}
+
+fn gamma_curve(gamma: f32) -> [u8; 256] {
+    let mut curve = [0; 256];
+    for (i, value) in curve.iter_mut().enumerate() {
+        *value = (libm::powf(i as f32 / 255.0, gamma) * 255.0 + 0.5) as u8;
+    }
+    curve
+}
+
+// Worked in integers so that it can be evaluated at compile time. With the
+// input scaled to a lightness of 0-100, luminance is ((L + 16) / 116)^3 above
+// L = 8 and L / 903.3 below it.
+const fn cie_curve() -> [u8; 256] {
+    const SCALE: u64 = 116 * 255;
+    let mut curve = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let lightness = i as u64 * 100;
+        curve[i] = if lightness <= 8 * 255 {
+            ((i as u64 * 1000 + 4516) / 9033) as u8
+        } else {
+            let t = lightness + 16 * 255;
+            ((t * t * t * 255 + SCALE * SCALE * SCALE / 2) / (SCALE * SCALE * SCALE)) as u8
+        };
+        i += 1;
+    }
+    curve
+}
@@ -0,0 +1,45 @@
+use super::Color;
+
+// How colors are rounded when they're cut down to the few bits the panels can
+// show. Without dithering the low bits are simply dropped, which bands
+// gradients.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, defmt::Format)]
+pub enum Dithering {
+    #[default]
+    None,
+    // Rounds each pixel up or down according to its place in a 4x4 Bayer
+    // matrix, trading banding for a fixed fine pattern.
+    Ordered,
+    // Same as ordered, but the pattern moves every frame so that each pixel
+    // averages out to its exact color over time.
+    Temporal,
+}
+
+const BAYER_4X4: [[u8; 4]; 4] = [
+    [0, 8, 2, 10],
+    [12, 4, 14, 6],
+    [3, 11, 1, 9],
+    [15, 7, 13, 5],
+];
+
+// Odd, so every pixel goes through all 16 thresholds every 16 frames.
+const TEMPORAL_STEP: usize = 7;
+
+impl Dithering {
+    // Adds up to one quantization step to each channel of a color that's about
+    // to be cut down to `bits` bits per channel.
+    #[inline(always)]
+    pub fn apply(self, color: Color, x: usize, y: usize, phase: usize, bits: usize) -> Color {
+        let threshold = match self {
+            Dithering::None => return color,
+            Dithering::Ordered => BAYER_4X4[y % 4][x % 4] as usize,
+            Dithering::Temporal => (BAYER_4X4[y % 4][x % 4] as usize + phase * TEMPORAL_STEP) % 16,
+        };
+        let offset = (threshold << (8 - bits) >> 4) as u8;
+        Color::from_rgb(
+            color.r().saturating_add(offset),
+            color.g().saturating_add(offset),
+            color.b().saturating_add(offset),
+        )
+    }
+}
@@ -1,53 +1,38 @@
-use core::convert::Infallible;
-
 use defmt::info;
-use embedded_hal::digital::v2::OutputPin;
 use rp2040_hal::{
-    gpio::{bank0, Pin, PinState, PullDownDisabled, PushPullOutput},
+    gpio::{bank0, Pin, PullDownDisabled},
     Timer,
 };
 
-use super::{spec, Color};
-
-pub type Matrix = [[Color; spec::VIRTUAL_WIDTH]; spec::VIRTUAL_HEIGHT];
-
-type ColorPin<Id> = Pin<Id, PushPullOutput>;
-type RowPin<Id> = Pin<Id, PushPullOutput>;
-type ClkPin<Id> = Pin<Id, PushPullOutput>;
-type LatchPin<Id> = Pin<Id, PushPullOutput>;
-type OePin<Id> = Pin<Id, PushPullOutput>;
-
-fn bit_state(bit: u8) -> PinState {
-    if bit > 0 {
-        PinState::High
-    } else {
-        PinState::Low
-    }
-}
+use super::{
+    bitplanes::Bitplanes,
+    frame::Frame,
+    pins::{DefaultPins, Hub75Pins},
+    scanner::Scanner,
+    spec,
+    spec::Geometry,
+    ColorCorrection, Dithering, Matrix, Modulation, Pipeline,
+};
 
-pub struct Driver<'a> {
-    matrix: Matrix,
-    tick_counter: usize,
+pub struct Driver<
+    'a,
+    P = DefaultPins,
+    const W: usize = { spec::VIRTUAL_WIDTH },
+    const H: usize = { spec::VIRTUAL_HEIGHT },
+    F = Matrix<W, H>,
+> {
+    pipeline: Pipeline<W, H, F>,
+    bitplanes: Bitplanes<W, H>,
     timer: &'a Timer,
-    r1: ColorPin<bank0::Gpio2>,
-    r2: ColorPin<bank0::Gpio3>,
-    g1: ColorPin<bank0::Gpio4>,
-    g2: ColorPin<bank0::Gpio5>,
-    b1: ColorPin<bank0::Gpio6>,
-    b2: ColorPin<bank0::Gpio7>,
-    a: RowPin<bank0::Gpio10>,
-    b: RowPin<bank0::Gpio11>,
-    c: RowPin<bank0::Gpio12>,
-    d: RowPin<bank0::Gpio13>,
-    clk: ClkPin<bank0::Gpio8>,
-    latch: LatchPin<bank0::Gpio9>,
-    oe: OePin<bank0::Gpio14>, // low = enabled, high = disabled
+    scanner: Scanner<P>,
 }
 
-impl<'a> Driver<'a> {
+impl<'a, const W: usize, const H: usize, F: Frame<W, H>> Driver<'a, DefaultPins, W, H, F> {
+    // Uses the board's own wiring; see DefaultPins.
     #[allow(clippy::too_many_arguments)]
     pub fn init(
         timer: &'a Timer,
+        geometry: Geometry,
         r1: Pin<bank0::Gpio2, PullDownDisabled>,
         r2: Pin<bank0::Gpio3, PullDownDisabled>,
         g1: Pin<bank0::Gpio4, PullDownDisabled>,
@@ -62,38 +47,89 @@ impl<'a> Driver<'a> {
         d: Pin<bank0::Gpio13, PullDownDisabled>,
         oe: Pin<bank0::Gpio14, PullDownDisabled>,
     ) -> Self {
+        let pins = DefaultPins::new(r1, r2, g1, g2, b1, b2, clk, latch, a, b, c, d, oe);
+        Self::with_pins(timer, geometry, pins)
+    }
+}
+
+impl<'a, P: Hub75Pins, const W: usize, const H: usize, F: Frame<W, H>> Driver<'a, P, W, H, F> {
+    // Panics if the geometry doesn't fit a W by H canvas.
+    pub fn with_pins(timer: &'a Timer, geometry: Geometry, mut pins: P) -> Self {
+        geometry.validate(W, H).unwrap();
+        geometry.chip.configure(&mut pins, geometry.physical_width()).unwrap();
         Self {
-            matrix: [[Color::black(); spec::VIRTUAL_WIDTH]; spec::VIRTUAL_HEIGHT],
-            tick_counter: 0,
+            pipeline: Pipeline::new(),
+            bitplanes: Bitplanes::new(geometry),
             timer,
-            r1: r1.into_push_pull_output_in_state(PinState::Low),
-            r2: r2.into_push_pull_output_in_state(PinState::Low),
-            g1: g1.into_push_pull_output_in_state(PinState::Low),
-            g2: g2.into_push_pull_output_in_state(PinState::Low),
-            b1: b1.into_push_pull_output_in_state(PinState::Low),
-            b2: b2.into_push_pull_output_in_state(PinState::Low),
-            a: a.into_push_pull_output_in_state(PinState::Low),
-            b: b.into_push_pull_output_in_state(PinState::Low),
-            c: c.into_push_pull_output_in_state(PinState::Low),
-            d: d.into_push_pull_output_in_state(PinState::Low),
-            clk: clk.into_push_pull_output_in_state(PinState::Low),
-            latch: latch.into_push_pull_output_in_state(PinState::Low),
-            oe: oe.into_push_pull_output_in_state(PinState::Low),
+            scanner: Scanner::new(pins),
         }
     }
 
-    pub fn draw_loop(&mut self, mut render: impl FnMut(&mut Matrix)) {
+    pub fn set_modulation(&mut self, modulation: Modulation) {
+        self.pipeline.set_modulation(modulation);
+        self.commit();
+    }
+
+    // Defaults to ColorCorrection::CIE.
+    pub fn set_color_correction(&mut self, correction: ColorCorrection) {
+        self.pipeline.set_color_correction(correction);
+        self.commit();
+    }
+
+    pub fn set_dithering(&mut self, dithering: Dithering) {
+        self.pipeline.set_dithering(dithering);
+        self.commit();
+    }
+
+    // 255 is full brightness. Dims by cutting the time each row is lit, so
+    // colors keep their full depth.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.bitplanes.set_brightness(brightness);
+    }
+
+    pub fn set_preserve_back_buffer(&mut self, preserve: bool) {
+        self.pipeline.set_preserve_back_buffer(preserve);
+    }
+
+    pub fn back_buffer(&mut self) -> &mut F {
+        self.pipeline.back_buffer()
+    }
+
+    // Makes the back buffer visible. Scan-out only happens between calls to
+    // refresh, so this always lands on a frame boundary.
+    pub fn present(&mut self) {
+        self.pipeline.swap();
+        self.commit();
+    }
+
+    // See Pipeline::tick for what happens with temporal dithering.
+    pub fn refresh(&mut self) {
+        self.scanner.draw(&self.bitplanes).unwrap();
+        self.pipeline.tick(self.scanner.tick_counter(), &mut self.bitplanes);
+    }
+
+    fn commit(&mut self) {
+        self.pipeline.commit(&mut self.bitplanes);
+    }
+
+    // The render function draws into the back buffer and returns whether it
+    // should be presented. Idle ticks cost nothing but scan-out.
+    pub fn draw_loop(&mut self, mut render: impl FnMut(&mut F) -> bool) {
         let start = self.timer.get_counter();
         loop {
-            render(&mut self.matrix);
-            self.draw().unwrap();
+            if render(self.pipeline.back_buffer()) {
+                self.present();
+            }
+            self.refresh();
 
-            if self.tick_counter == 1000 {
+            let tick_counter = self.scanner.tick_counter();
+            if tick_counter == 1000 {
                 let tick_us =
-                    (self.timer.get_counter() - start).to_micros() as usize / self.tick_counter;
+                    (self.timer.get_counter() - start).to_micros() as usize / tick_counter;
                 let tick_hz = 1_000_000 / tick_us;
+                let geometry = self.bitplanes.geometry();
                 let cell_hz =
-                    tick_hz * spec::PHYSICAL_WIDTH * spec::PHYSICAL_HEIGHT / 2;
+                    tick_hz * geometry.physical_width() * geometry.physical_height() / 2;
                 info!(
                     "Tick speed: {} Hz ({} μs) | Cell speed: {} {}",
                     tick_us,
@@ -108,64 +144,4 @@ impl<'a> Driver<'a> {
             }
         }
     }
-
-    #[inline(always)]
-    fn draw(&mut self) -> Result<(), Infallible> {
-        const MODULO: usize = spec::COLOR_BITMASK as usize + 1;
-        let div = (self.tick_counter % MODULO) as u32;
-        self.tick_counter += 1;
-
-        for y_pair in 0..(spec::PHYSICAL_HEIGHT / 2) {
-            self.oe.set_low()?;
-            self.latch.set_low()?;
-
-            // Draw the row pair
-            for x in 0..spec::PHYSICAL_WIDTH {
-                let n1 = self.get_color(x, y_pair).hex();
-                let n2 = self.get_color(x, y_pair + (spec::PHYSICAL_HEIGHT / 2)).hex();
-
-                const SHIFT: usize = 8 - spec::COLOR_BITMASK.count_ones() as usize;
-                let (r1, g1, b1) = (
-                    div < n1 >> 16 >> SHIFT,
-                    div < (n1 >> 8 >> SHIFT) & spec::COLOR_BITMASK,
-                    div < (n1 >> SHIFT) & spec::COLOR_BITMASK,
-                );
-                let (r2, g2, b2) = (
-                    div < n2 >> 16 >> SHIFT,
-                    div < (n2 >> 8 >> SHIFT) & spec::COLOR_BITMASK,
-                    div < (n2 >> SHIFT) & spec::COLOR_BITMASK,
-                );
-
-                self.r1.set_state(r1.into())?;
-                self.r2.set_state(r2.into())?;
-                self.g1.set_state(g1.into())?;
-                self.g2.set_state(g2.into())?;
-                self.b1.set_state(b1.into())?;
-                self.b2.set_state(b2.into())?;
-
-                self.clk.set_high()?;
-                cortex_m::asm::delay(1);
-                self.clk.set_low()?;
-                cortex_m::asm::delay(1);
-            }
-
-            self.oe.set_high()?;
-            self.latch.set_high()?;
-            cortex_m::asm::delay(1);
-
-            // Select the row pair
-            self.a.set_state(bit_state(0b0001 & y_pair as u8))?;
-            self.b.set_state(bit_state(0b0010 & y_pair as u8))?;
-            self.c.set_state(bit_state(0b0100 & y_pair as u8))?;
-            self.d.set_state(bit_state(0b1000 & y_pair as u8))?;
-        }
-
-        self.oe.set_high()?;
-        Ok(())
-    }
-
-    fn get_color(&self, x: usize, y: usize) -> Color {
-        let (x, y) = spec::physical_to_virtual(x, y);
-        self.matrix[y][x]
-    }
 }
@@ -0,0 +1,95 @@
+use core::ops::Range;
+
+use super::{spec, Color};
+
+pub type Matrix<const W: usize = { spec::VIRTUAL_WIDTH }, const H: usize = { spec::VIRTUAL_HEIGHT }> =
+    [[Color; W]; H];
+
+// Anything a frame can be drawn into and converted to bitplanes from. A Matrix
+// is the simplest, but takes four bytes per pixel.
+pub trait Frame<const W: usize, const H: usize>: Copy {
+    const BLANK: Self;
+
+    fn pixel(&self, x: usize, y: usize) -> Color;
+}
+
+impl<const W: usize, const H: usize> Frame<W, H> for Matrix<W, H> {
+    const BLANK: Self = [[Color::black(); W]; H];
+
+    #[inline(always)]
+    fn pixel(&self, x: usize, y: usize) -> Color {
+        self[y][x]
+    }
+}
+
+// Two bytes per pixel, with 5 bits of red, 6 of green and 5 of blue. That's
+// still more than the panels can show with threshold modulation.
+#[derive(Copy, Clone)]
+pub struct Rgb565Frame<const W: usize, const H: usize> {
+    pub pixels: [[u16; W]; H],
+}
+
+impl<const W: usize, const H: usize> Rgb565Frame<W, H> {
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Color) {
+        self.pixels[y][x] = color.to_rgb565();
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        self.pixels = [[color.to_rgb565(); W]; H];
+    }
+}
+
+impl<const W: usize, const H: usize> Frame<W, H> for Rgb565Frame<W, H> {
+    const BLANK: Self = Self {
+        pixels: [[0; W]; H],
+    };
+
+    #[inline(always)]
+    fn pixel(&self, x: usize, y: usize) -> Color {
+        Color::from_rgb565(self.pixels[y][x])
+    }
+}
+
+// One byte per pixel, indexing into a palette of 256 colors. Changing the
+// palette recolors the whole frame without touching the pixels, which makes
+// for cheap palette cycling.
+#[derive(Copy, Clone)]
+pub struct IndexedFrame<const W: usize, const H: usize> {
+    pub pixels: [[u8; W]; H],
+    pub palette: [Color; 256],
+}
+
+impl<const W: usize, const H: usize> IndexedFrame<W, H> {
+    pub fn set_index(&mut self, x: usize, y: usize, index: u8) {
+        self.pixels[y][x] = index;
+    }
+
+    pub fn clear(&mut self, index: u8) {
+        self.pixels = [[index; W]; H];
+    }
+
+    pub fn set_palette(&mut self, palette: [Color; 256]) {
+        self.palette = palette;
+    }
+
+    // Moves the colors in a range of palette entries up by `steps`, wrapping
+    // around, so that anything drawn with them appears to flow.
+    pub fn cycle_palette(&mut self, range: Range<usize>, steps: usize) {
+        let entries = &mut self.palette[range];
+        if !entries.is_empty() {
+            entries.rotate_right(steps % entries.len());
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Frame<W, H> for IndexedFrame<W, H> {
+    const BLANK: Self = Self {
+        pixels: [[0; W]; H],
+        palette: [Color::black(); 256],
+    };
+
+    #[inline(always)]
+    fn pixel(&self, x: usize, y: usize) -> Color {
+        self.palette[self.pixels[y][x] as usize]
+    }
+}
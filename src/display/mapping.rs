@@ -0,0 +1,157 @@
+// Clockwise rotation of a panel as mounted, relative to its pixels' natural
+// orientation with the input connector on the left.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Rotation {
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+// Where one panel in the chain sits in the virtual canvas. The position is the
+// top left corner of the panel's footprint after flipping and rotating it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub struct Placement {
+    pub x: usize,
+    pub y: usize,
+    pub rotation: Rotation,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+impl Placement {
+    pub const fn at(x: usize, y: usize) -> Self {
+        Self {
+            x,
+            y,
+            rotation: Rotation::Deg0,
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+
+    pub const fn rotated(self, rotation: Rotation) -> Self {
+        Self { rotation, ..self }
+    }
+
+    // Flips are applied in the panel's own coordinates, before rotating.
+    pub const fn flipped(self, flip_x: bool, flip_y: bool) -> Self {
+        Self {
+            flip_x,
+            flip_y,
+            ..self
+        }
+    }
+
+    // Width and height the panel takes up in the canvas.
+    pub const fn size(&self, panel_width: usize, panel_height: usize) -> (usize, usize) {
+        match self.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => (panel_width, panel_height),
+            Rotation::Deg90 | Rotation::Deg270 => (panel_height, panel_width),
+        }
+    }
+
+    // Maps a pixel in the panel's own coordinates to the canvas.
+    #[inline(always)]
+    pub const fn to_virtual(
+        &self,
+        x: usize,
+        y: usize,
+        panel_width: usize,
+        panel_height: usize,
+    ) -> (usize, usize) {
+        let x = if self.flip_x { panel_width - x - 1 } else { x };
+        let y = if self.flip_y { panel_height - y - 1 } else { y };
+        let (x, y) = match self.rotation {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (panel_height - y - 1, x),
+            Rotation::Deg180 => (panel_width - x - 1, panel_height - y - 1),
+            Rotation::Deg270 => (y, panel_width - x - 1),
+        };
+        (self.x + x, self.y + y)
+    }
+
+    const fn overlaps(
+        &self,
+        other: &Placement,
+        panel_width: usize,
+        panel_height: usize,
+    ) -> bool {
+        let (w, h) = self.size(panel_width, panel_height);
+        let (other_w, other_h) = other.size(panel_width, panel_height);
+        self.x < other.x + other_w
+            && other.x < self.x + w
+            && self.y < other.y + other_h
+            && other.y < self.y + h
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum LayoutError {
+    // A panel sticks out past the edge of the canvas.
+    OutOfBounds { panel: usize },
+    // Two panels cover some of the same pixels.
+    Overlap { first: usize, second: usize },
+}
+
+// Smallest canvas that fits every panel in the layout.
+pub const fn bounds(layout: &[Placement], panel_width: usize, panel_height: usize) -> (usize, usize) {
+    let (mut width, mut height) = (0, 0);
+    let mut i = 0;
+    while i < layout.len() {
+        let (w, h) = layout[i].size(panel_width, panel_height);
+        if layout[i].x + w > width {
+            width = layout[i].x + w;
+        }
+        if layout[i].y + h > height {
+            height = layout[i].y + h;
+        }
+        i += 1;
+    }
+    (width, height)
+}
+
+pub fn validate(
+    layout: &[Placement],
+    panel_width: usize,
+    panel_height: usize,
+    width: usize,
+    height: usize,
+) -> Result<(), LayoutError> {
+    for (i, placement) in layout.iter().enumerate() {
+        let (w, h) = placement.size(panel_width, panel_height);
+        if placement.x + w > width || placement.y + h > height {
+            return Err(LayoutError::OutOfBounds { panel: i });
+        }
+        for (j, other) in layout.iter().enumerate().skip(i + 1) {
+            if placement.overlaps(other, panel_width, panel_height) {
+                return Err(LayoutError::Overlap { first: i, second: j });
+            }
+        }
+    }
+    Ok(())
+}
+
+// Lays N panels out in a grid, filling rows of `columns` panels from the top.
+// With serpentine set, the chain snakes back and forth, so every other row runs
+// right to left with its panels upside down.
+pub const fn grid<const N: usize>(
+    columns: usize,
+    panel_width: usize,
+    panel_height: usize,
+    serpentine: bool,
+) -> [Placement; N] {
+    let mut layout = [Placement::at(0, 0); N];
+    let mut i = 0;
+    while i < N {
+        let (row, column) = (i / columns, i % columns);
+        layout[i] = if serpentine && row % 2 == 1 {
+            Placement::at((columns - column - 1) * panel_width, row * panel_height)
+                .rotated(Rotation::Deg180)
+        } else {
+            Placement::at(column * panel_width, row * panel_height)
+        };
+        i += 1;
+    }
+    layout
+}
@@ -1,7 +1,42 @@
+pub mod mapping;
 pub mod spec;
+mod bitplanes;
+mod buffers;
+mod canvas;
+mod chip;
+#[cfg(feature = "rp2040")]
+mod core1;
+mod correction;
 mod color;
+mod dithering;
+#[cfg(feature = "rp2040")]
 mod driver;
+mod frame;
+mod modulation;
+mod pins;
+mod pipeline;
+#[cfg(feature = "rp2040")]
+mod pio_driver;
+mod scanner;
+#[cfg(feature = "std")]
+pub mod sim;
 
+pub use bitplanes::Bitplanes;
+pub use buffers::FrameBuffers;
+pub use canvas::Canvas;
+pub use chip::PanelChip;
+#[cfg(feature = "rp2040")]
+pub use core1::Core1Driver;
+pub use correction::ColorCorrection;
+pub use dithering::Dithering;
+#[cfg(feature = "rp2040")]
 pub use driver::Driver;
-pub use driver::Matrix;
+pub use frame::{Frame, Matrix, IndexedFrame, Rgb565Frame};
+pub use modulation::Modulation;
+#[cfg(feature = "rp2040")]
+pub use pins::DefaultPins;
+pub use pins::{Hub75Pins, NoPin, Pins};
+pub use pipeline::Pipeline;
+#[cfg(feature = "rp2040")]
+pub use pio_driver::PioDriver;
 pub use color::Color;
\ No newline at end of file
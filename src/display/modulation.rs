@@ -0,0 +1,12 @@
+// How color channels are turned into on/off time for each LED.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Modulation {
+	// Every tick draws the whole panel once, lighting a pixel if its channel is
+	// above the tick's threshold. Needs 2^bits passes for a full color cycle,
+	// so limited to spec::COLOR_BITMASK.
+	#[default]
+	Threshold,
+	// Draws one bitplane per channel bit, keeping each plane lit for a time
+	// proportional to its significance. Needs only spec::BCM_BITS passes.
+	BinaryCoded,
+}
\ No newline at end of file
@@ -0,0 +1,232 @@
+use core::{convert::Infallible, fmt::Debug, marker::PhantomData};
+
+use embedded_hal::digital::v2::{OutputPin, PinState};
+#[cfg(feature = "rp2040")]
+use rp2040_hal::gpio::{bank0, Pin, PullDownDisabled, PushPullOutput};
+
+// Everything the bit-banged drivers need to drive a HUB75 connector.
+// Implemented by Pins for any set of output pins, but a board with something
+// faster (e.g. writing the data lines through a single SIO register) can
+// implement it directly.
+pub trait Hub75Pins {
+    type Error: Debug;
+
+    // The low six bits of the column are R1, R2, G1, G2, B1, B2.
+    fn set_data(&mut self, column: u8) -> Result<(), Self::Error>;
+    fn set_clk(&mut self, state: PinState) -> Result<(), Self::Error>;
+    fn set_latch(&mut self, state: PinState) -> Result<(), Self::Error>;
+    // Low = enabled, high = disabled.
+    fn set_oe(&mut self, state: PinState) -> Result<(), Self::Error>;
+    // Drives A through E with the low five bits of the row pair.
+    fn set_address(&mut self, y_pair: usize) -> Result<(), Self::Error>;
+    // Waits for roughly this many CPU cycles, to let the lines settle or to
+    // keep a row lit.
+    fn delay(&mut self, cycles: u32);
+}
+
+// One output pin per HUB75 line. The pins can be of different types as long as
+// they share an error type. Panels with 16 or fewer row pairs don't have an E
+// line, in which case it can be left as a NoPin.
+pub struct Pins<R1, R2, G1, G2, B1, B2, Clk, Latch, A, B, C, D, E, Oe> {
+    pub r1: R1,
+    pub r2: R2,
+    pub g1: G1,
+    pub g2: G2,
+    pub b1: B1,
+    pub b2: B2,
+    pub clk: Clk,
+    pub latch: Latch,
+    pub a: A,
+    pub b: B,
+    pub c: C,
+    pub d: D,
+    pub e: E,
+    pub oe: Oe,
+}
+
+// Stands in for a line that isn't connected. Writes to it are ignored.
+pub struct NoPin<E = Infallible>(PhantomData<E>);
+
+impl<E> NoPin<E> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E> Default for NoPin<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> OutputPin for NoPin<E> {
+    type Error = E;
+
+    fn set_low(&mut self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+// The wiring this board was built with: data on GPIO2-7, then CLK, LAT, A-D and
+// OE on GPIO8-14. There's no E line; see with_e for panels that need one.
+#[cfg(feature = "rp2040")]
+pub type DefaultPins = Pins<
+    Pin<bank0::Gpio2, PushPullOutput>,
+    Pin<bank0::Gpio3, PushPullOutput>,
+    Pin<bank0::Gpio4, PushPullOutput>,
+    Pin<bank0::Gpio5, PushPullOutput>,
+    Pin<bank0::Gpio6, PushPullOutput>,
+    Pin<bank0::Gpio7, PushPullOutput>,
+    Pin<bank0::Gpio8, PushPullOutput>,
+    Pin<bank0::Gpio9, PushPullOutput>,
+    Pin<bank0::Gpio10, PushPullOutput>,
+    Pin<bank0::Gpio11, PushPullOutput>,
+    Pin<bank0::Gpio12, PushPullOutput>,
+    Pin<bank0::Gpio13, PushPullOutput>,
+    NoPin,
+    Pin<bank0::Gpio14, PushPullOutput>,
+>;
+
+#[cfg(feature = "rp2040")]
+impl DefaultPins {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        r1: Pin<bank0::Gpio2, PullDownDisabled>,
+        r2: Pin<bank0::Gpio3, PullDownDisabled>,
+        g1: Pin<bank0::Gpio4, PullDownDisabled>,
+        g2: Pin<bank0::Gpio5, PullDownDisabled>,
+        b1: Pin<bank0::Gpio6, PullDownDisabled>,
+        b2: Pin<bank0::Gpio7, PullDownDisabled>,
+        clk: Pin<bank0::Gpio8, PullDownDisabled>,
+        latch: Pin<bank0::Gpio9, PullDownDisabled>,
+        a: Pin<bank0::Gpio10, PullDownDisabled>,
+        b: Pin<bank0::Gpio11, PullDownDisabled>,
+        c: Pin<bank0::Gpio12, PullDownDisabled>,
+        d: Pin<bank0::Gpio13, PullDownDisabled>,
+        oe: Pin<bank0::Gpio14, PullDownDisabled>,
+    ) -> Self {
+        Self {
+            r1: r1.into_push_pull_output_in_state(PinState::Low),
+            r2: r2.into_push_pull_output_in_state(PinState::Low),
+            g1: g1.into_push_pull_output_in_state(PinState::Low),
+            g2: g2.into_push_pull_output_in_state(PinState::Low),
+            b1: b1.into_push_pull_output_in_state(PinState::Low),
+            b2: b2.into_push_pull_output_in_state(PinState::Low),
+            clk: clk.into_push_pull_output_in_state(PinState::Low),
+            latch: latch.into_push_pull_output_in_state(PinState::Low),
+            a: a.into_push_pull_output_in_state(PinState::Low),
+            b: b.into_push_pull_output_in_state(PinState::Low),
+            c: c.into_push_pull_output_in_state(PinState::Low),
+            d: d.into_push_pull_output_in_state(PinState::Low),
+            e: NoPin::new(),
+            oe: oe.into_push_pull_output_in_state(PinState::Low),
+        }
+    }
+}
+
+impl<Error, R1, R2, G1, G2, B1, B2, Clk, Latch, A, B, C, D, Oe>
+    Pins<R1, R2, G1, G2, B1, B2, Clk, Latch, A, B, C, D, NoPin<Error>, Oe>
+{
+    // Adds an E line, for 1/32 scan panels.
+    #[allow(clippy::type_complexity)]
+    pub fn with_e<E>(self, e: E) -> Pins<R1, R2, G1, G2, B1, B2, Clk, Latch, A, B, C, D, E, Oe> {
+        Pins {
+            r1: self.r1,
+            r2: self.r2,
+            g1: self.g1,
+            g2: self.g2,
+            b1: self.b1,
+            b2: self.b2,
+            clk: self.clk,
+            latch: self.latch,
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e,
+            oe: self.oe,
+        }
+    }
+}
+
+fn bit_state(bit: u8) -> PinState {
+    if bit > 0 {
+        PinState::High
+    } else {
+        PinState::Low
+    }
+}
+
+impl<Error, R1, R2, G1, G2, B1, B2, Clk, Latch, A, B, C, D, E, Oe> Hub75Pins
+    for Pins<R1, R2, G1, G2, B1, B2, Clk, Latch, A, B, C, D, E, Oe>
+where
+    Error: Debug,
+    R1: OutputPin<Error = Error>,
+    R2: OutputPin<Error = Error>,
+    G1: OutputPin<Error = Error>,
+    G2: OutputPin<Error = Error>,
+    B1: OutputPin<Error = Error>,
+    B2: OutputPin<Error = Error>,
+    Clk: OutputPin<Error = Error>,
+    Latch: OutputPin<Error = Error>,
+    A: OutputPin<Error = Error>,
+    B: OutputPin<Error = Error>,
+    C: OutputPin<Error = Error>,
+    D: OutputPin<Error = Error>,
+    E: OutputPin<Error = Error>,
+    Oe: OutputPin<Error = Error>,
+{
+    type Error = Error;
+
+    #[inline(always)]
+    fn set_data(&mut self, column: u8) -> Result<(), Error> {
+        self.r1.set_state(bit_state(column & 0b000001))?;
+        self.r2.set_state(bit_state(column & 0b000010))?;
+        self.g1.set_state(bit_state(column & 0b000100))?;
+        self.g2.set_state(bit_state(column & 0b001000))?;
+        self.b1.set_state(bit_state(column & 0b010000))?;
+        self.b2.set_state(bit_state(column & 0b100000))?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn set_clk(&mut self, state: PinState) -> Result<(), Error> {
+        self.clk.set_state(state)
+    }
+
+    #[inline(always)]
+    fn set_latch(&mut self, state: PinState) -> Result<(), Error> {
+        self.latch.set_state(state)
+    }
+
+    #[inline(always)]
+    fn set_oe(&mut self, state: PinState) -> Result<(), Error> {
+        self.oe.set_state(state)
+    }
+
+    #[inline(always)]
+    fn set_address(&mut self, y_pair: usize) -> Result<(), Error> {
+        self.a.set_state(bit_state(0b0001 & y_pair as u8))?;
+        self.b.set_state(bit_state(0b0010 & y_pair as u8))?;
+        self.c.set_state(bit_state(0b0100 & y_pair as u8))?;
+        self.d.set_state(bit_state(0b1000 & y_pair as u8))?;
+        self.e.set_state(bit_state(0b10000 & y_pair as u8))?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn delay(&mut self, cycles: u32) {
+        // cortex_m's delay is unimplemented!() anywhere but ARM, and pins on
+        // the host (e.g. in tests) don't need accurate timing anyway.
+        #[cfg(target_arch = "arm")]
+        cortex_m::asm::delay(cycles);
+        #[cfg(not(target_arch = "arm"))]
+        for _ in 0..cycles {
+            core::hint::spin_loop();
+        }
+    }
+}
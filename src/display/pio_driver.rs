@@ -0,0 +1,396 @@
+use defmt::info;
+use rp2040_hal::{
+    gpio::{bank0, FunctionPio0, Pin, PullDownDisabled},
+    pac,
+    pio::{
+        Buffers, PIOBuilder, PIOExt, PinDir, Running, ShiftDirection, StateMachine, Tx, PIO, SM0,
+        SM1,
+    },
+    Timer,
+};
+
+use super::{
+    bitplanes::Bitplanes, frame::Frame, pins::DefaultPins, spec, ColorCorrection, Dithering,
+    Matrix, Modulation, Pipeline,
+};
+
+// Both state machines run at sys_clk / CLOCK_DIVISOR. The data program takes
+// two cycles per column, so at 125 MHz this clocks pixels out at 12.5 MHz.
+const CLOCK_DIVISOR: u16 = 5;
+
+const PASSES: usize = spec::COLOR_BITMASK as usize + 1;
+// There's no pin left next to A-D for an E line, so 1/16 scan is as far as
+// this wiring goes.
+const MAX_ROW_PAIRS: usize = 16;
+
+// Each stream is a pair of channels: one feeds a state machine's TX FIFO and
+// chains into the other, which rewrites the first one's read address to
+// restart it. Scan-out loops forever without any CPU involvement.
+const DATA_CH: usize = 0;
+const DATA_RELOAD_CH: usize = 1;
+const ROW_CH: usize = 2;
+const ROW_RELOAD_CH: usize = 3;
+
+const DATA_PIN_BASE: u8 = 2; // R1, R2, G1, G2, B1, B2
+const CLK_PIN: u8 = 8; // followed by LAT
+const ADDR_PIN_BASE: u8 = 10; // A, B, C, D
+const OE_PIN: u8 = 14;
+
+type Pio0Pin<Id> = Pin<Id, FunctionPio0>;
+
+pub struct PioDriver<
+    'a,
+    const W: usize = { spec::VIRTUAL_WIDTH },
+    const H: usize = { spec::VIRTUAL_HEIGHT },
+    F = Matrix<W, H>,
+> {
+    pipeline: Pipeline<W, H, F>,
+    bitplanes: &'static mut [Bitplanes<W, H>; 2],
+    active: usize,
+    frame_address: &'static mut u32,
+    control: &'static mut [u32],
+    row_on_cycles: u32,
+    frame_counter: usize,
+    timer: &'a Timer,
+    _pio: PIO<pac::PIO0>,
+    _data_sm: StateMachine<(pac::PIO0, SM0), Running>,
+    _row_sm: StateMachine<(pac::PIO0, SM1), Running>,
+    _data_tx: Tx<(pac::PIO0, SM0)>,
+    _row_tx: Tx<(pac::PIO0, SM1)>,
+    dma: pac::DMA,
+    _pins: (
+        Pio0Pin<bank0::Gpio2>,
+        Pio0Pin<bank0::Gpio3>,
+        Pio0Pin<bank0::Gpio4>,
+        Pio0Pin<bank0::Gpio5>,
+        Pio0Pin<bank0::Gpio6>,
+        Pio0Pin<bank0::Gpio7>,
+        Pio0Pin<bank0::Gpio8>,
+        Pio0Pin<bank0::Gpio9>,
+        Pio0Pin<bank0::Gpio10>,
+        Pio0Pin<bank0::Gpio11>,
+        Pio0Pin<bank0::Gpio12>,
+        Pio0Pin<bank0::Gpio13>,
+        Pio0Pin<bank0::Gpio14>,
+    ),
+}
+
+impl<'a, const W: usize, const H: usize, F: Frame<W, H>> PioDriver<'a, W, H, F> {
+    // Panics if the bitplanes' geometry doesn't fit a W by H canvas.
+    #[allow(clippy::too_many_arguments)]
+    pub fn init(
+        timer: &'a Timer,
+        bitplanes: &'static mut [Bitplanes<W, H>; 2],
+        pio0: pac::PIO0,
+        dma: pac::DMA,
+        resets: &mut pac::RESETS,
+        r1: Pin<bank0::Gpio2, PullDownDisabled>,
+        r2: Pin<bank0::Gpio3, PullDownDisabled>,
+        g1: Pin<bank0::Gpio4, PullDownDisabled>,
+        g2: Pin<bank0::Gpio5, PullDownDisabled>,
+        b1: Pin<bank0::Gpio6, PullDownDisabled>,
+        b2: Pin<bank0::Gpio7, PullDownDisabled>,
+        clk: Pin<bank0::Gpio8, PullDownDisabled>,
+        latch: Pin<bank0::Gpio9, PullDownDisabled>,
+        a: Pin<bank0::Gpio10, PullDownDisabled>,
+        b: Pin<bank0::Gpio11, PullDownDisabled>,
+        c: Pin<bank0::Gpio12, PullDownDisabled>,
+        d: Pin<bank0::Gpio13, PullDownDisabled>,
+        oe: Pin<bank0::Gpio14, PullDownDisabled>,
+    ) -> Self {
+        let geometry = *bitplanes[0].geometry();
+        geometry.validate(W, H).unwrap();
+
+        // The chips are configured by bit-banging before the pins are handed
+        // over to the PIO.
+        let mut pins = DefaultPins::new(r1, r2, g1, g2, b1, b2, clk, latch, a, b, c, d, oe);
+        geometry.chip.configure(&mut pins, geometry.physical_width()).unwrap();
+        let pins = (
+            pins.r1.into_mode(),
+            pins.r2.into_mode(),
+            pins.g1.into_mode(),
+            pins.g2.into_mode(),
+            pins.b1.into_mode(),
+            pins.b2.into_mode(),
+            pins.clk.into_mode(),
+            pins.latch.into_mode(),
+            pins.a.into_mode(),
+            pins.b.into_mode(),
+            pins.c.into_mode(),
+            pins.d.into_mode(),
+            pins.oe.into_mode(),
+        );
+
+        assert!(geometry.row_pairs() <= MAX_ROW_PAIRS);
+
+        // How long OE stays asserted for each row, in row state machine
+        // cycles. Roughly matches the time it takes to shift out the next row,
+        // so neither state machine spends long waiting on the other.
+        let row_on_cycles = geometry.physical_width() as u32 * 2;
+
+        // The DMA streams are sized for threshold modulation when they start, so
+        // that's the only scheme supported here.
+        let control_words = PASSES * geometry.row_pairs();
+        let control =
+            &mut cortex_m::singleton!(: [u32; PASSES * MAX_ROW_PAIRS] = [0; PASSES * MAX_ROW_PAIRS])
+                .unwrap()[..control_words];
+        for (i, word) in control.iter_mut().enumerate() {
+            let y_pair = (i % geometry.row_pairs()) as u32;
+            *word = row_on_cycles << 5 | y_pair;
+        }
+
+        // Shifts out one row of pixel pairs, one byte per column, then waits
+        // for the row program to blank the panel before latching it.
+        let data_program = pio_proc::pio_asm!(
+            ".side_set 2", // CLK, LAT
+            "    out y, 32           side 0b00", // columns - 1, written once at startup
+            ".wrap_target",
+            "    mov x, y            side 0b00",
+            "column:",
+            "    out pins, 8         side 0b00",
+            "    jmp x-- column      side 0b01",
+            "    wait 1 irq 5        side 0b00",
+            "    nop                 side 0b10 [1]",
+            "    irq set 4           side 0b00",
+            ".wrap",
+        );
+        // Takes one control word per row (address in the low 5 bits, OE
+        // on-time above that) and lights the row once the data program has
+        // latched it.
+        let row_program = pio_proc::pio_asm!(
+            ".side_set 1", // OE, active low
+            ".wrap_target",
+            "    pull block          side 1",
+            "    irq set 5           side 1",
+            "    wait 1 irq 4        side 1",
+            "    out pins, 5         side 1",
+            "    out x, 27           side 1",
+            "on:",
+            "    jmp x-- on          side 0",
+            ".wrap",
+        );
+
+        let (mut pio, sm0, sm1, _, _) = pio0.split(resets);
+        let data_program = pio.install(&data_program.program).unwrap();
+        let row_program = pio.install(&row_program.program).unwrap();
+
+        let (mut data_sm, _, mut data_tx) = PIOBuilder::from_program(data_program)
+            .out_pins(DATA_PIN_BASE, 6)
+            .side_set_pin_base(CLK_PIN)
+            .out_shift_direction(ShiftDirection::Right)
+            .autopull(true)
+            .pull_threshold(32)
+            .buffers(Buffers::OnlyTx)
+            .clock_divisor_fixed_point(CLOCK_DIVISOR, 0)
+            .build(sm0);
+        data_sm.set_pindirs((DATA_PIN_BASE..=CLK_PIN + 1).map(|id| (id, PinDir::Output)));
+
+        let (mut row_sm, _, row_tx) = PIOBuilder::from_program(row_program)
+            .out_pins(ADDR_PIN_BASE, 4)
+            .side_set_pin_base(OE_PIN)
+            .out_shift_direction(ShiftDirection::Right)
+            .buffers(Buffers::OnlyTx)
+            .clock_divisor_fixed_point(CLOCK_DIVISOR, 0)
+            .build(sm1);
+        row_sm.set_pindirs((ADDR_PIN_BASE..=OE_PIN).map(|id| (id, PinDir::Output)));
+
+        data_tx.write(geometry.physical_width() as u32 - 1);
+
+        resets.reset.modify(|_, w| w.dma().clear_bit());
+        while resets.reset_done.read().dma().bit_is_clear() {}
+
+        let frame_address =
+            cortex_m::singleton!(: u32 = bitplanes[0].words().as_ptr() as u32).unwrap();
+        let control_address = cortex_m::singleton!(: u32 = control.as_ptr() as u32).unwrap();
+        start_stream(
+            &dma,
+            DATA_CH,
+            DATA_RELOAD_CH,
+            frame_address,
+            bitplanes[0].words().len(),
+            data_tx.fifo_address(),
+            data_tx.dreq_value(),
+        );
+        start_stream(
+            &dma,
+            ROW_CH,
+            ROW_RELOAD_CH,
+            control_address,
+            control_words,
+            row_tx.fifo_address(),
+            row_tx.dreq_value(),
+        );
+
+        // Only threshold modulation fits the control words set up above.
+        let mut pipeline = Pipeline::new();
+        pipeline.set_modulation(Modulation::Threshold);
+
+        Self {
+            pipeline,
+            bitplanes,
+            active: 0,
+            frame_address,
+            control,
+            row_on_cycles,
+            frame_counter: 0,
+            timer,
+            _pio: pio,
+            _data_sm: data_sm.start(),
+            _row_sm: row_sm.start(),
+            _data_tx: data_tx,
+            _row_tx: row_tx,
+            dma,
+            _pins: pins,
+        }
+    }
+
+    // 255 is full brightness. Dims by shortening the OE on-time in the row
+    // control words, which the DMA picks up from the next row on. Rows still
+    // light up for a single cycle at 0.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        let on_cycles = self.row_on_cycles * brightness as u32 / 255;
+        for word in self.control.iter_mut() {
+            let y_pair = *word & 0b11111;
+            // SAFETY: Only read by the row DMA channel, and a word write is
+            // atomic.
+            unsafe { core::ptr::write_volatile(word, on_cycles << 5 | y_pair) };
+        }
+    }
+
+    // Defaults to ColorCorrection::CIE. Takes effect from the next present.
+    pub fn set_color_correction(&mut self, correction: ColorCorrection) {
+        self.pipeline.set_color_correction(correction);
+    }
+
+    // Takes effect from the next present.
+    pub fn set_dithering(&mut self, dithering: Dithering) {
+        self.pipeline.set_dithering(dithering);
+    }
+
+    pub fn set_preserve_back_buffer(&mut self, preserve: bool) {
+        self.pipeline.set_preserve_back_buffer(preserve);
+    }
+
+    pub fn back_buffer(&mut self) -> &mut F {
+        self.pipeline.back_buffer()
+    }
+
+    // Makes the back buffer visible. The DMA keeps streaming the current
+    // bitplanes until the end of the frame and only then moves on to the new
+    // ones, so a frame is never shown half-drawn.
+    pub fn present(&mut self) {
+        self.pipeline.swap();
+        self.show_front();
+    }
+
+    // Commits the front buffer and points the DMA at it.
+    fn show_front(&mut self) {
+        self.wait_for_swap();
+
+        let next = 1 - self.active;
+        self.pipeline.commit(&mut self.bitplanes[next]);
+        self.pipeline.advance_phase();
+        // SAFETY: Only read by the reload DMA channel, and a word write is
+        // atomic, so it sees either the old or the new address.
+        unsafe {
+            core::ptr::write_volatile(
+                self.frame_address,
+                self.bitplanes[next].words().as_ptr() as u32,
+            );
+        }
+        self.active = next;
+    }
+
+    // Blocks until the DMA has started streaming the most recently presented
+    // bitplanes, after which the other ones are free to commit into.
+    fn wait_for_swap(&self) {
+        let words = self.bitplanes[self.active].words();
+        let start = words.as_ptr() as u32;
+        let end = start + (words.len() * 4) as u32;
+        while !(start..=end).contains(&self.dma.ch[DATA_CH].ch_read_addr.read().bits()) {}
+    }
+
+    // The render function draws into the back buffer and returns whether it
+    // should be presented. With temporal dithering, idle iterations recommit
+    // the front buffer to keep the pattern moving.
+    pub fn draw_loop(&mut self, mut render: impl FnMut(&mut F) -> bool) {
+        let start = self.timer.get_counter();
+        loop {
+            if !render(self.pipeline.back_buffer()) {
+                if self.pipeline.dithering() == Dithering::Temporal {
+                    self.show_front();
+                }
+                continue;
+            }
+            self.present();
+
+            self.frame_counter += 1;
+            if self.frame_counter == 1000 {
+                let frame_us =
+                    (self.timer.get_counter() - start).to_micros() as usize / self.frame_counter;
+                info!(
+                    "Frame speed: {} Hz ({} μs)",
+                    1_000_000 / frame_us,
+                    frame_us
+                );
+            }
+        }
+    }
+}
+
+// Sets up a DMA channel to feed `len` words starting at `*read_address` into a
+// PIO FIFO, with a second channel that restarts it each time it finishes.
+fn start_stream(
+    dma: &pac::DMA,
+    ch: usize,
+    reload_ch: usize,
+    read_address: &'static u32,
+    len: usize,
+    fifo: *const u32,
+    dreq: u8,
+) {
+    let stream = &dma.ch[ch];
+    let reload = &dma.ch[reload_ch];
+
+    reload
+        .ch_read_addr
+        .write(|w| unsafe { w.bits(read_address as *const u32 as u32) });
+    reload
+        .ch_write_addr
+        .write(|w| unsafe { w.bits(stream.ch_al3_read_addr_trig.as_ptr() as u32) });
+    reload.ch_trans_count.write(|w| unsafe { w.bits(1) });
+    reload.ch_al1_ctrl.write(|w| unsafe {
+        w.data_size()
+            .size_word()
+            .incr_read()
+            .clear_bit()
+            .incr_write()
+            .clear_bit()
+            .treq_sel()
+            .permanent()
+            .chain_to()
+            .bits(reload_ch as u8)
+            .en()
+            .set_bit()
+    });
+
+    stream.ch_write_addr.write(|w| unsafe { w.bits(fifo as u32) });
+    stream.ch_trans_count.write(|w| unsafe { w.bits(len as u32) });
+    stream.ch_al1_ctrl.write(|w| unsafe {
+        w.data_size()
+            .size_word()
+            .incr_read()
+            .set_bit()
+            .incr_write()
+            .clear_bit()
+            .treq_sel()
+            .bits(dreq)
+            .chain_to()
+            .bits(reload_ch as u8)
+            .en()
+            .set_bit()
+    });
+    stream
+        .ch_al3_read_addr_trig
+        .write(|w| unsafe { w.bits(*read_address) });
+}
@@ -0,0 +1,103 @@
+use super::{
+    bitplanes::Bitplanes, frame::Frame, spec, ColorCorrection, Dithering, FrameBuffers, Matrix,
+    Modulation,
+};
+
+// Everything between drawing a frame and the bitplanes that get scanned out:
+// the front and back buffers, and the settings and dithering phase the front
+// buffer is committed with. Drivers only differ in where their bitplanes live
+// and how those get to the panels.
+pub struct Pipeline<
+    const W: usize = { spec::VIRTUAL_WIDTH },
+    const H: usize = { spec::VIRTUAL_HEIGHT },
+    F = Matrix<W, H>,
+> {
+    buffers: FrameBuffers<W, H, F>,
+    modulation: Modulation,
+    correction: ColorCorrection,
+    dithering: Dithering,
+    phase: usize,
+}
+
+impl<const W: usize, const H: usize, F: Frame<W, H>> Pipeline<W, H, F> {
+    pub fn new() -> Self {
+        Self {
+            buffers: FrameBuffers::new(),
+            modulation: Modulation::default(),
+            correction: ColorCorrection::default(),
+            dithering: Dithering::default(),
+            phase: 0,
+        }
+    }
+
+    pub fn modulation(&self) -> Modulation {
+        self.modulation
+    }
+
+    pub fn set_modulation(&mut self, modulation: Modulation) {
+        self.modulation = modulation;
+    }
+
+    // Defaults to ColorCorrection::CIE.
+    pub fn set_color_correction(&mut self, correction: ColorCorrection) {
+        self.correction = correction;
+    }
+
+    pub fn dithering(&self) -> Dithering {
+        self.dithering
+    }
+
+    pub fn set_dithering(&mut self, dithering: Dithering) {
+        self.dithering = dithering;
+    }
+
+    pub fn set_preserve_back_buffer(&mut self, preserve: bool) {
+        self.buffers.set_preserve(preserve);
+    }
+
+    pub fn back_buffer(&mut self) -> &mut F {
+        self.buffers.back()
+    }
+
+    // Makes the back buffer the front one. It still has to be committed to
+    // show up.
+    pub fn swap(&mut self) {
+        self.buffers.swap();
+    }
+
+    // Converts the front buffer into bitplanes with the current settings.
+    pub fn commit(&self, bitplanes: &mut Bitplanes<W, H>) {
+        bitplanes.commit(
+            self.buffers.front(),
+            self.modulation,
+            &self.correction,
+            self.dithering,
+            self.phase,
+        );
+    }
+
+    // Moves temporal dithering on to its next pattern. Drivers that commit
+    // into fresh bitplanes every time do this after each commit.
+    pub fn advance_phase(&mut self) {
+        self.phase += 1;
+    }
+
+    // For drivers that scan out of the same bitplanes they commit into, called
+    // after every tick. With temporal dithering, the front buffer is
+    // recommitted after every frame to move the pattern along, at the cost of
+    // some refresh rate.
+    pub fn tick(&mut self, tick_counter: usize, bitplanes: &mut Bitplanes<W, H>) {
+        if self.dithering == Dithering::Temporal
+            && tick_counter.is_multiple_of(bitplanes.plane_count())
+        {
+            self.advance_phase();
+            self.commit(bitplanes);
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, F: Frame<W, H>> Default for Pipeline<W, H, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,154 @@
+use embedded_hal::digital::v2::PinState;
+
+use super::{bitplanes::Bitplanes, pins::Hub75Pins, Modulation};
+
+// How long the least significant bitplane stays lit with binary-coded
+// modulation, in CPU cycles. Each following plane doubles this.
+const BCM_BASE_CYCLES: u32 = 16;
+
+// Bit-bangs bitplanes out to the panels. Holds nothing but the pins, so it's
+// small enough to hand off to another core.
+pub struct Scanner<P> {
+    tick_counter: usize,
+    // What's left over from dimming the base on-time, in 255ths of a cycle,
+    // carried into the next tick so low brightnesses still average out right.
+    base_remainder: u32,
+    pins: P,
+}
+
+impl<P: Hub75Pins> Scanner<P> {
+    pub fn new(pins: P) -> Self {
+        Self {
+            tick_counter: 0,
+            base_remainder: 0,
+            pins,
+        }
+    }
+
+    // Draws one tick's worth of the bitplanes: a single pass with threshold
+    // modulation, or every plane with binary-coded modulation.
+    #[inline(always)]
+    pub fn draw<const W: usize, const H: usize>(
+        &mut self,
+        bitplanes: &Bitplanes<W, H>,
+    ) -> Result<(), P::Error> {
+        match bitplanes.modulation() {
+            Modulation::Threshold => self.draw_threshold(bitplanes),
+            Modulation::BinaryCoded => self.draw_binary_coded(bitplanes),
+        }
+    }
+
+    pub fn tick_counter(&self) -> usize {
+        self.tick_counter
+    }
+
+    pub fn pins(&self) -> &P {
+        &self.pins
+    }
+
+    pub fn pins_mut(&mut self) -> &mut P {
+        &mut self.pins
+    }
+
+    fn draw_threshold<const W: usize, const H: usize>(
+        &mut self,
+        bitplanes: &Bitplanes<W, H>,
+    ) -> Result<(), P::Error> {
+        let plane = self.tick_counter % bitplanes.plane_count();
+        self.tick_counter += 1;
+
+        // The previous row stays lit while the next one is shifted in, for as
+        // many columns as the brightness allows.
+        let columns = bitplanes.words_per_row() * 4;
+        let lit_columns = columns * bitplanes.brightness() as usize / 255;
+
+        for y_pair in 0..bitplanes.geometry().row_pairs() {
+            if lit_columns > 0 {
+                self.pins.set_oe(PinState::Low)?;
+            }
+            self.pins.set_latch(PinState::Low)?;
+
+            self.shift_row(bitplanes.row(plane, y_pair), lit_columns)?;
+
+            self.pins.set_oe(PinState::High)?;
+            self.pins.set_latch(PinState::High)?;
+            self.pins.delay(1);
+
+            self.select_row(y_pair)?;
+        }
+
+        self.pins.set_oe(PinState::High)?;
+        Ok(())
+    }
+
+    // Unlike the threshold scheme, the panel stays blank while a plane is
+    // shifted in so that each plane's on-time is exactly its weight.
+    fn draw_binary_coded<const W: usize, const H: usize>(
+        &mut self,
+        bitplanes: &Bitplanes<W, H>,
+    ) -> Result<(), P::Error> {
+        self.tick_counter += 1;
+
+        let columns = bitplanes.words_per_row() * 4;
+        // Dims the base on-time rather than each plane's, so every plane
+        // keeps a pulse in proportion to its weight instead of the low ones
+        // rounding down to nothing.
+        let scaled = BCM_BASE_CYCLES * bitplanes.brightness() as u32 + self.base_remainder;
+        let base_cycles = scaled / 255;
+        self.base_remainder = scaled % 255;
+
+        for y_pair in 0..bitplanes.geometry().row_pairs() {
+            for plane in 0..bitplanes.plane_count() {
+                self.shift_row(bitplanes.row(plane, y_pair), columns)?;
+
+                self.pins.set_latch(PinState::High)?;
+                self.select_row(y_pair)?;
+                self.pins.delay(1);
+                self.pins.set_latch(PinState::Low)?;
+
+                let on_cycles = base_cycles * bitplanes.weight(plane);
+                if on_cycles > 0 {
+                    self.pins.set_oe(PinState::Low)?;
+                    self.pins.delay(on_cycles);
+                    self.pins.set_oe(PinState::High)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Blanks the panel once lit_columns columns have been shifted in.
+    #[inline(always)]
+    fn shift_row(&mut self, row: &[u32], lit_columns: usize) -> Result<(), P::Error> {
+        let mut column = 0;
+        for mut word in row.iter().copied() {
+            for _ in 0..4 {
+                if column == lit_columns {
+                    self.pins.set_oe(PinState::High)?;
+                }
+                self.shift_column(word as u8)?;
+                word >>= 8;
+                column += 1;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn shift_column(&mut self, column: u8) -> Result<(), P::Error> {
+        self.pins.set_data(column)?;
+
+        self.pins.set_clk(PinState::High)?;
+        self.pins.delay(1);
+        self.pins.set_clk(PinState::Low)?;
+        self.pins.delay(1);
+        Ok(())
+    }
+
+    // Select the row pair
+    #[inline(always)]
+    fn select_row(&mut self, y_pair: usize) -> Result<(), P::Error> {
+        self.pins.set_address(y_pair)
+    }
+}
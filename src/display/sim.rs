@@ -0,0 +1,321 @@
+// Runs the display pipeline on the host, against simulated panels instead of
+// real pins. The same scanner that drives the hardware drives SimPins, which
+// keeps track of what the panels would show and, optionally, every change on
+// the HUB75 lines.
+
+use std::{collections::VecDeque, convert::Infallible, io};
+
+use embedded_hal::digital::v2::PinState;
+
+use super::{
+    bitplanes::Bitplanes, scanner::Scanner, spec::Geometry, Color, ColorCorrection, Dithering,
+    Frame, Hub75Pins, Matrix, Modulation, Pipeline,
+};
+
+// One change on the HUB75 lines, timed in CPU cycles from the start.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub time: u64,
+    pub line: Line,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Line {
+    // R1, R2, G1, G2, B1, B2 in the low six bits.
+    Data(u8),
+    Clk(PinState),
+    Latch(PinState),
+    Oe(PinState),
+    Address(usize),
+}
+
+// Simulated panels. Columns are clocked into a shift register, copied out on
+// the rising edge of LAT, and lit at the selected row pair whenever OE is low.
+// Time only passes in delays, which is also where light is accumulated.
+pub struct SimPins {
+    columns: usize,
+    row_pairs: usize,
+    shift: VecDeque<u8>,
+    latched: Vec<u8>,
+    data: u8,
+    clk: PinState,
+    latch: PinState,
+    oe: PinState,
+    address: usize,
+    time: u64,
+    // Cycles each physical pixel's red, green and blue LEDs have been on for.
+    light: Vec<[u64; 3]>,
+    // Cycles each row pair has been lit for, i.e. the most any pixel in it
+    // could have been on for.
+    row_light: Vec<u64>,
+    events: Option<Vec<Event>>,
+}
+
+impl SimPins {
+    pub fn new(geometry: &Geometry, record_events: bool) -> Self {
+        let columns = geometry.physical_width();
+        let row_pairs = geometry.row_pairs();
+        Self {
+            columns,
+            row_pairs,
+            shift: VecDeque::with_capacity(columns + 1),
+            latched: vec![0; columns],
+            data: 0,
+            clk: PinState::Low,
+            latch: PinState::Low,
+            oe: PinState::High,
+            address: 0,
+            time: 0,
+            light: vec![[0; 3]; columns * row_pairs * 2],
+            row_light: vec![0; row_pairs],
+            events: record_events.then(Vec::new),
+        }
+    }
+
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    // Empty unless events are being recorded.
+    pub fn events(&self) -> &[Event] {
+        self.events.as_deref().unwrap_or(&[])
+    }
+
+    pub fn clear_events(&mut self) {
+        if let Some(events) = &mut self.events {
+            events.clear();
+        }
+    }
+
+    pub fn clear_light(&mut self) {
+        self.light.fill([0; 3]);
+        self.row_light.fill(0);
+    }
+
+    // How bright a physical pixel has looked on average since the light was
+    // last cleared, relative to a pixel that was on the whole time its row was
+    // lit.
+    pub fn pixel(&self, x: usize, y: usize) -> Color {
+        let lit = self.row_light[y % self.row_pairs];
+        if lit == 0 {
+            return Color::black();
+        }
+        let [r, g, b] = self.light[y * self.columns + x].map(|on| (on * 255 / lit) as u8);
+        Color::from_rgb(r, g, b)
+    }
+
+    fn record(&mut self, line: Line) {
+        let time = self.time;
+        if let Some(events) = &mut self.events {
+            events.push(Event { time, line });
+        }
+    }
+}
+
+impl Hub75Pins for SimPins {
+    type Error = Infallible;
+
+    fn set_data(&mut self, column: u8) -> Result<(), Infallible> {
+        if column != self.data {
+            self.data = column;
+            self.record(Line::Data(column));
+        }
+        Ok(())
+    }
+
+    fn set_clk(&mut self, state: PinState) -> Result<(), Infallible> {
+        if state != self.clk {
+            self.clk = state;
+            self.record(Line::Clk(state));
+            if state == PinState::High {
+                self.shift.push_back(self.data);
+                if self.shift.len() > self.columns {
+                    self.shift.pop_front();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn set_latch(&mut self, state: PinState) -> Result<(), Infallible> {
+        if state != self.latch {
+            self.latch = state;
+            self.record(Line::Latch(state));
+            if state == PinState::High {
+                for (latched, column) in self.latched.iter_mut().zip(&self.shift) {
+                    *latched = *column;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn set_oe(&mut self, state: PinState) -> Result<(), Infallible> {
+        if state != self.oe {
+            self.oe = state;
+            self.record(Line::Oe(state));
+        }
+        Ok(())
+    }
+
+    fn set_address(&mut self, y_pair: usize) -> Result<(), Infallible> {
+        let y_pair = y_pair % self.row_pairs;
+        if y_pair != self.address {
+            self.address = y_pair;
+            self.record(Line::Address(y_pair));
+        }
+        Ok(())
+    }
+
+    fn delay(&mut self, cycles: u32) {
+        let cycles = cycles as u64;
+        self.time += cycles;
+        if self.oe == PinState::High {
+            return;
+        }
+
+        self.row_light[self.address] += cycles;
+        for (x, column) in self.latched.iter().enumerate() {
+            for (half, y) in [self.address, self.address + self.row_pairs].into_iter().enumerate() {
+                let light = &mut self.light[y * self.columns + x];
+                for (channel, on) in light.iter_mut().enumerate() {
+                    if (column >> (channel * 2 + half)) & 1 > 0 {
+                        *on += cycles;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Works like Driver, through the same Pipeline, but scans out into SimPins,
+// and can capture what the panels show.
+pub struct Simulator<const W: usize, const H: usize, F = Matrix<W, H>> {
+    pipeline: Pipeline<W, H, F>,
+    bitplanes: Box<Bitplanes<W, H>>,
+    scanner: Scanner<SimPins>,
+}
+
+impl<const W: usize, const H: usize, F: Frame<W, H>> Simulator<W, H, F> {
+    // Panics if the geometry doesn't fit a W by H canvas.
+    pub fn new(geometry: Geometry, record_events: bool) -> Self {
+        geometry.validate(W, H).unwrap();
+        let mut pins = SimPins::new(&geometry, record_events);
+        geometry.chip.configure(&mut pins, geometry.physical_width()).unwrap();
+        Self {
+            pipeline: Pipeline::new(),
+            bitplanes: Box::new(Bitplanes::new(geometry)),
+            scanner: Scanner::new(pins),
+        }
+    }
+
+    pub fn set_modulation(&mut self, modulation: Modulation) {
+        self.pipeline.set_modulation(modulation);
+        self.commit();
+    }
+
+    pub fn set_color_correction(&mut self, correction: ColorCorrection) {
+        self.pipeline.set_color_correction(correction);
+        self.commit();
+    }
+
+    pub fn set_dithering(&mut self, dithering: Dithering) {
+        self.pipeline.set_dithering(dithering);
+        self.commit();
+    }
+
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.bitplanes.set_brightness(brightness);
+    }
+
+    pub fn set_preserve_back_buffer(&mut self, preserve: bool) {
+        self.pipeline.set_preserve_back_buffer(preserve);
+    }
+
+    pub fn back_buffer(&mut self) -> &mut F {
+        self.pipeline.back_buffer()
+    }
+
+    pub fn present(&mut self) {
+        self.pipeline.swap();
+        self.commit();
+    }
+
+    // Scans out a single tick, same as Driver::refresh.
+    pub fn refresh(&mut self) {
+        self.scanner.draw(&self.bitplanes).unwrap();
+        self.pipeline.tick(self.scanner.tick_counter(), &mut self.bitplanes);
+    }
+
+    pub fn pins(&self) -> &SimPins {
+        self.scanner.pins()
+    }
+
+    pub fn pins_mut(&mut self) -> &mut SimPins {
+        self.scanner.pins_mut()
+    }
+
+    // Scans out enough ticks to show every plane once and returns what the
+    // panels looked like, mapped back onto the canvas. Parts of the canvas
+    // that aren't covered by a panel are left black.
+    pub fn capture(&mut self) -> Matrix<W, H> {
+        let ticks = match self.pipeline.modulation() {
+            Modulation::Threshold => self.bitplanes.plane_count(),
+            Modulation::BinaryCoded => 1,
+        };
+
+        // With threshold modulation the last row of a tick is only lit at
+        // the start of the next one, so get one tick ahead before measuring.
+        self.refresh();
+        self.pins_mut().clear_light();
+        for _ in 0..ticks {
+            self.refresh();
+        }
+
+        let geometry = *self.bitplanes.geometry();
+        let mut matrix = [[Color::black(); W]; H];
+        for y in 0..geometry.physical_height() {
+            for x in 0..geometry.physical_width() {
+                let (virtual_x, virtual_y) = geometry.physical_to_virtual(x, y);
+                matrix[virtual_y][virtual_x] = self.pins().pixel(x, y);
+            }
+        }
+        matrix
+    }
+
+    fn commit(&mut self) {
+        self.pipeline.commit(&mut self.bitplanes);
+    }
+}
+
+// Writes a frame as a binary PPM, which most image viewers can open and is
+// trivial to diff.
+pub fn write_ppm<const W: usize, const H: usize>(
+    frame: &impl Frame<W, H>,
+    mut out: impl io::Write,
+) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", W, H)?;
+    for y in 0..H {
+        for x in 0..W {
+            out.write_all(&frame.pixel(x, y).to_rgb888())?;
+        }
+    }
+    Ok(())
+}
+
+pub fn write_png<const W: usize, const H: usize>(
+    frame: &impl Frame<W, H>,
+    out: impl io::Write,
+) -> Result<(), png::EncodingError> {
+    let mut encoder = png::Encoder::new(out, W as u32, H as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut data = Vec::with_capacity(W * H * 3);
+    for y in 0..H {
+        for x in 0..W {
+            data.extend_from_slice(&frame.pixel(x, y).to_rgb888());
+        }
+    }
+    encoder.write_header()?.write_image_data(&data)
+}
@@ -1,19 +1,150 @@
+use super::{
+	chip::PanelChip,
+	mapping::{self, LayoutError, Placement, Rotation},
+};
+
 // Depends on the performance of the display. Higher values result in a
 // larger color space, but also a lot more flickering.
 pub const COLOR_BITMASK: u32 = 0b00001111;
 
-pub const PHYSICAL_WIDTH: usize = 128;
-pub const PHYSICAL_HEIGHT: usize = 32;
+// Bits per channel when using binary-coded modulation. Each bit only adds one
+// bitplane rather than doubling the number of passes, so this can be much
+// higher than COLOR_BITMASK.
+pub const BCM_BITS: usize = 8;
+
+// Size of the virtual canvas for the default geometry, TWO_PANEL_64X64.
 pub const VIRTUAL_WIDTH: usize = 64;
 pub const VIRTUAL_HEIGHT: usize = 64;
 
-pub const fn physical_to_virtual(physical_x: usize, physical_y: usize) -> (usize, usize) {
-	if physical_x < VIRTUAL_WIDTH {
-		(
-			VIRTUAL_WIDTH - physical_x - 1,
-			PHYSICAL_HEIGHT - physical_y - 1,
+// Describes a chain of identical HUB75 panels and how they're arranged into
+// the virtual canvas that gets drawn on.
+//
+// Physically, the chain is one long strip scanned a row pair at a time, with
+// one row pair per address. Usually each half of a panel has as many rows as
+// there are addresses, so the strip is panel_width * layout.len() by
+// panel_height. Panels with fewer addresses, like outdoor 32 row panels
+// scanned 1/8, light several rows in each half per address. Their shift
+// registers run through those rows one after another, top to bottom, so the
+// strip is that many times longer and only scan * 2 rows tall. The layout has
+// one placement per panel, in chain order.
+#[derive(Copy, Clone)]
+pub struct Geometry {
+	pub panel_width: usize,
+	pub panel_height: usize,
+	// Number of row addresses, i.e. 16 for a 1/16 scan panel.
+	pub scan: usize,
+	pub layout: &'static [Placement],
+	pub chip: PanelChip,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum GeometryError {
+	// The canvas isn't the size the layout adds up to.
+	SizeMismatch,
+	// Each address has to select the same number of rows in each half of the
+	// panel, and there have to be 8, 16 or 32 addresses.
+	UnsupportedScan,
+	// Columns are packed four to a word.
+	UnalignedWidth,
+	Layout(LayoutError),
+}
+
+impl Geometry {
+	// The scan is derived from the panel height: 1/8 for 16 rows, 1/16 for 32
+	// and 1/32 for 64. The last needs an E line. Use with_scan for panels that
+	// light more than one row per address in each half.
+	pub const fn new(panel_width: usize, panel_height: usize, layout: &'static [Placement]) -> Self {
+		Self {
+			panel_width,
+			panel_height,
+			scan: panel_height / 2,
+			layout,
+			chip: PanelChip::Generic,
+		}
+	}
+
+	pub const fn with_chip(self, chip: PanelChip) -> Self {
+		Self { chip, ..self }
+	}
+
+	// Number of row addresses, e.g. 8 for a 32 row panel scanned 1/8.
+	pub const fn with_scan(self, scan: usize) -> Self {
+		Self { scan, ..self }
+	}
+
+	pub const fn chain_length(&self) -> usize {
+		self.layout.len()
+	}
+
+	// Rows each address lights in each half of a panel.
+	pub const fn rows_per_address(&self) -> usize {
+		self.panel_height / 2 / self.scan
+	}
+
+	// Columns shifted in per row pair, across the whole chain.
+	pub const fn physical_width(&self) -> usize {
+		self.panel_width * self.rows_per_address() * self.chain_length()
+	}
+
+	pub const fn physical_height(&self) -> usize {
+		self.scan * 2
+	}
+
+	pub const fn row_pairs(&self) -> usize {
+		self.scan
+	}
+
+	pub const fn virtual_size(&self) -> (usize, usize) {
+		mapping::bounds(self.layout, self.panel_width, self.panel_height)
+	}
+
+	// The row of a panel that a physical row shows, in the given stretch of
+	// panel_width columns along that panel's part of the strip.
+	pub const fn panel_y(&self, physical_y: usize, segment: usize) -> usize {
+		physical_y / self.scan * (self.panel_height / 2) + segment * self.scan + physical_y % self.scan
+	}
+
+	pub fn physical_to_virtual(&self, physical_x: usize, physical_y: usize) -> (usize, usize) {
+		let segment = physical_x / self.panel_width;
+		self.layout[segment / self.rows_per_address()].to_virtual(
+			physical_x % self.panel_width,
+			self.panel_y(physical_y, segment % self.rows_per_address()),
+			self.panel_width,
+			self.panel_height,
 		)
-	} else {
-		(physical_x - VIRTUAL_WIDTH, physical_y + PHYSICAL_HEIGHT)
 	}
-}
\ No newline at end of file
+
+	pub fn validate(&self, width: usize, height: usize) -> Result<(), GeometryError> {
+		if (width, height) != self.virtual_size() {
+			return Err(GeometryError::SizeMismatch);
+		}
+		if !matches!(self.scan, 8 | 16 | 32) || !self.panel_height.is_multiple_of(self.scan * 2) {
+			return Err(GeometryError::UnsupportedScan);
+		}
+		if !self.physical_width().is_multiple_of(4) {
+			return Err(GeometryError::UnalignedWidth);
+		}
+		mapping::validate(self.layout, self.panel_width, self.panel_height, width, height)
+			.map_err(GeometryError::Layout)
+	}
+}
+
+// A single 64x32 panel.
+pub const SINGLE_64X32: Geometry = Geometry::new(64, 32, &[Placement::at(0, 0)]);
+
+// A single 64x64 panel, scanned 1/32.
+pub const SINGLE_64X64: Geometry = Geometry::new(64, 64, &[Placement::at(0, 0)]);
+
+// A single 32x16 panel, scanned 1/8.
+pub const SINGLE_32X16: Geometry = Geometry::new(32, 16, &[Placement::at(0, 0)]);
+
+// Two 64x32 panels stacked into a 64x64 square. The first panel in the chain
+// is mounted on top, upside down.
+pub const TWO_PANEL_64X64: Geometry = Geometry::new(
+	64,
+	32,
+	&[
+		Placement::at(0, 0).rotated(Rotation::Deg180),
+		Placement::at(0, 32),
+	],
+);
\ No newline at end of file
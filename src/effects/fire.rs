@@ -0,0 +1,85 @@
+use super::{math::Rng, Palette, Ticker, FRAME_US};
+use crate::{display::Matrix, scene::Scene};
+
+// Flames rising from the bottom edge. Every step, heat moves up a row,
+// drifting sideways and cooling down on the way.
+pub struct Fire<const W: usize, const H: usize> {
+    heat: [[u8; W]; H],
+    palette: Palette,
+    cooling: u8,
+    intensity: u8,
+    rng: Rng,
+    ticker: Ticker,
+}
+
+impl<const W: usize, const H: usize> Fire<W, H> {
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            heat: [[0; W]; H],
+            palette: Palette::Heat,
+            // Flames about two thirds of the way up.
+            cooling: if H < 3 { 255 } else { (3 * 255 / H) as u8 },
+            intensity: 255,
+            rng: Rng::new(seed),
+            ticker: Ticker::new(FRAME_US),
+        }
+    }
+
+    pub const fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    // The most heat a pixel can lose moving up a row. Bigger makes shorter
+    // flames.
+    pub const fn with_cooling(mut self, cooling: u8) -> Self {
+        self.cooling = cooling;
+        self
+    }
+
+    // How hot the bottom edge is. Turning it down to 0 lets the fire die out.
+    pub fn set_intensity(&mut self, intensity: u8) {
+        self.intensity = intensity;
+    }
+
+    fn step(&mut self) {
+        for y in 0..H.saturating_sub(1) {
+            for x in 0..W {
+                let below = self.heat[y + 1][x];
+                let cooling = self.rng.below(self.cooling as u32 + 1) as u8;
+                // Sideways by a pixel either way, or not at all.
+                let x = (x + self.rng.below(3) as usize)
+                    .saturating_sub(1)
+                    .min(W - 1);
+                self.heat[y][x] = below.saturating_sub(cooling);
+            }
+        }
+
+        if let Some(bottom) = self.heat.last_mut() {
+            for heat in bottom {
+                let flicker = 255 - self.rng.below(96) as u8;
+                *heat = (flicker as u16 * self.intensity as u16 / 255) as u8;
+            }
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Scene<Matrix<W, H>> for Fire<W, H> {
+    fn update(&mut self, dt: u32) -> bool {
+        let steps = self.ticker.advance(dt);
+        // Catching up on a long pause would look the same as a couple of
+        // steps anyway.
+        for _ in 0..steps.min(H as u32) {
+            self.step();
+        }
+        steps > 0
+    }
+
+    fn render(&mut self, matrix: &mut Matrix<W, H>) {
+        for (row, heat) in matrix.iter_mut().zip(&self.heat) {
+            for (pixel, &heat) in row.iter_mut().zip(heat) {
+                *pixel = self.palette.color(heat);
+            }
+        }
+    }
+}
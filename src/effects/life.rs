@@ -0,0 +1,131 @@
+use super::{math::Rng, Ticker};
+use crate::{
+    display::{Color, Matrix},
+    scene::Scene,
+};
+
+// Conway's Game of Life, wrapping around the edges. Once it settles down into
+// something that doesn't change, blinks or just has gliders going around, it
+// starts over from a new random soup.
+pub struct Life<const W: usize, const H: usize> {
+    cells: [[bool; W]; H],
+    color: Color,
+    // Out of 255, how many cells are alive in a new soup.
+    density: u8,
+    // How many generations in a row can have as many cells as a recent one
+    // before starting over. Things that have settled down repeat within a
+    // few generations, and soups that haven't hardly ever do it for long.
+    patience: u16,
+    stale: u16,
+    // How many cells the last few generations had.
+    history: [usize; 4],
+    rng: Rng,
+    ticker: Ticker,
+}
+
+impl<const W: usize, const H: usize> Life<W, H> {
+    pub fn new(seed: u32) -> Self {
+        let mut life = Self {
+            cells: [[false; W]; H],
+            color: Color::from_rgb(0, 255, 64),
+            density: 80,
+            patience: 30,
+            stale: 0,
+            history: [0; 4],
+            rng: Rng::new(seed),
+            ticker: Ticker::new(100_000),
+        };
+        life.reseed();
+        life
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    // How long each generation lasts, in microseconds.
+    pub fn with_interval(mut self, interval: u32) -> Self {
+        self.ticker = Ticker::new(interval);
+        self
+    }
+
+    // How crowded new soups are, out of 255, and how many stale generations
+    // to watch before starting over.
+    pub fn with_reseeding(mut self, density: u8, patience: u16) -> Self {
+        self.density = density;
+        self.patience = patience;
+        self.reseed();
+        self
+    }
+
+    // Starts over from a new random soup.
+    pub fn reseed(&mut self) {
+        for cell in self.cells.iter_mut().flatten() {
+            *cell = self.rng.byte() < self.density;
+        }
+        self.stale = 0;
+        self.history = [0; 4];
+    }
+
+    fn step(&mut self) {
+        // Only the rows on either side of the one being worked on are needed
+        // from the last generation.
+        let (Some(&first), Some(&last)) = (self.cells.first(), self.cells.last()) else {
+            return;
+        };
+        let mut above = last;
+        for y in 0..H {
+            let current = self.cells[y];
+            let below = if y + 1 < H { self.cells[y + 1] } else { first };
+            for x in 0..W {
+                let (left, right) = ((x + W - 1) % W, (x + 1) % W);
+                let neighbors = [
+                    above[left],
+                    above[x],
+                    above[right],
+                    current[left],
+                    current[right],
+                    below[left],
+                    below[x],
+                    below[right],
+                ]
+                .iter()
+                .filter(|&&alive| alive)
+                .count();
+                self.cells[y][x] = matches!((current[x], neighbors), (true, 2) | (_, 3));
+            }
+            above = current;
+        }
+
+        let population = self.cells.iter().flatten().filter(|&&alive| alive).count();
+        if self.history.contains(&population) {
+            self.stale += 1;
+        } else {
+            self.stale = 0;
+        }
+        self.history.rotate_left(1);
+        self.history[3] = population;
+        if self.stale >= self.patience {
+            self.reseed();
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Scene<Matrix<W, H>> for Life<W, H> {
+    fn update(&mut self, dt: u32) -> bool {
+        let steps = self.ticker.advance(dt);
+        if steps > 0 {
+            self.step();
+        }
+        steps > 0
+    }
+
+    fn render(&mut self, matrix: &mut Matrix<W, H>) {
+        for (row, cells) in matrix.iter_mut().zip(&self.cells) {
+            for (pixel, &alive) in row.iter_mut().zip(cells) {
+                *pixel = if alive { self.color } else { Color::black() };
+            }
+        }
+    }
+}
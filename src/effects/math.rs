@@ -0,0 +1,108 @@
+// Integer stand-ins for the floating point math effects usually use, since
+// the M0+ has no FPU. Angles are a byte for a whole turn, and most things come
+// out as a byte too.
+
+// A quarter of a sine wave, out of 127, calculated at compile time with
+// Bhaskara's approximation, which is well under a step off.
+const QUARTER_SINE: [u8; 65] = {
+    let mut table = [0; 65];
+    let mut i = 0;
+    while i <= 64 {
+        // Half a turn is 128 steps.
+        let (x, rest) = (i as i32, 128 - i as i32);
+        let numerator = 16 * x * rest;
+        let denominator = 5 * 128 * 128 - 4 * x * rest;
+        table[i] = ((numerator * 127 + denominator / 2) / denominator) as u8;
+        i += 1;
+    }
+    table
+};
+
+// -127 to 127.
+pub const fn sin8(angle: u8) -> i8 {
+    let index = angle as usize % 128;
+    let value = if index <= 64 {
+        QUARTER_SINE[index]
+    } else {
+        QUARTER_SINE[128 - index]
+    } as i8;
+    if angle < 128 {
+        value
+    } else {
+        -value
+    }
+}
+
+pub const fn cos8(angle: u8) -> i8 {
+    sin8(angle.wrapping_add(64))
+}
+
+// A sine wave moved up to go from 0 to 254, which is what palettes want.
+pub const fn wave8(angle: u8) -> u8 {
+    (sin8(angle) as i16 + 127) as u8
+}
+
+// A random looking value for every point, different for every seed.
+pub const fn hash(x: i32, y: i32, seed: u32) -> u32 {
+    let mut hash = (x as u32).wrapping_mul(0x9e37_79b1)
+        ^ (y as u32).wrapping_mul(0x85eb_ca77)
+        ^ seed.wrapping_mul(0xc2b2_ae3d);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x2c1b_3c6d);
+    hash ^= hash >> 12;
+    hash = hash.wrapping_mul(0x297a_2d39);
+    hash ^ hash >> 15
+}
+
+// Smoothly varying value noise, with a lattice point every 256 steps of x and
+// y. Out of 255.
+pub fn noise(x: i32, y: i32, seed: u32) -> u8 {
+    let (cell_x, cell_y) = (x >> 8, y >> 8);
+    let (tx, ty) = (smooth((x & 0xff) as u8), smooth((y & 0xff) as u8));
+    let corner = |dx, dy| (hash(cell_x + dx, cell_y + dy, seed) >> 24) as i32;
+    let top = lerp8(corner(0, 0), corner(1, 0), tx);
+    let bottom = lerp8(corner(0, 1), corner(1, 1), tx);
+    lerp8(top, bottom, ty) as u8
+}
+
+// Eases in and out, so noise doesn't have creases at lattice points.
+const fn smooth(t: u8) -> i32 {
+    let t = t as i32;
+    t * t * (3 * 256 - 2 * t) / (256 * 256)
+}
+
+const fn lerp8(from: i32, to: i32, t: i32) -> i32 {
+    from + (to - from) * t / 256
+}
+
+// Xorshift: small, fast and plenty random enough for effects.
+#[derive(Copy, Clone, Debug)]
+pub struct Rng(u32);
+
+impl Rng {
+    pub const fn new(seed: u32) -> Self {
+        // Zero would stay zero forever.
+        Self(if seed == 0 { 0x1234_5678 } else { seed })
+    }
+
+    pub fn u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+
+    // From 0 up to but not including `n`, which shouldn't be 0.
+    pub fn below(&mut self, n: u32) -> u32 {
+        ((self.u32() as u64 * n as u64) >> 32) as u32
+    }
+
+    pub fn byte(&mut self) -> u8 {
+        (self.u32() >> 24) as u8
+    }
+
+    // True one time in `n`.
+    pub fn one_in(&mut self, n: u32) -> bool {
+        self.below(n) == 0
+    }
+}
@@ -0,0 +1,116 @@
+// Generative visuals, each one a Scene that draws a whole Matrix. They're all
+// integer math, with parameters to tune how they look and move.
+
+mod fire;
+mod life;
+pub mod math;
+mod noise;
+mod plasma;
+mod rain;
+mod rainbow;
+mod starfield;
+
+pub use fire::Fire;
+pub use life::Life;
+pub use noise::NoiseField;
+pub use plasma::Plasma;
+pub use rain::DigitalRain;
+pub use rainbow::Rainbow;
+pub use starfield::Starfield;
+
+use crate::display::Color;
+
+// How often effects that move continuously are redrawn. 30 fps.
+const FRAME_US: u32 = 33_333;
+
+// Turns a value out of 255 into a color.
+#[derive(Copy, Clone, Debug)]
+pub enum Palette {
+    // All the way around the color wheel.
+    Rainbow,
+    // Black through red and yellow to white, like something burning.
+    Heat,
+    // Evenly spaced colors, blended between. Needs at least one.
+    Gradient(&'static [Color]),
+}
+
+impl Palette {
+    pub fn color(&self, value: u8) -> Color {
+        match *self {
+            Palette::Rainbow => Color::from_hsv((value as u32 * 360 / 256) as u16, 255, 255),
+            Palette::Heat => {
+                // A third of the way each for red, green and blue.
+                let value = value as u16 * 3;
+                let channel = |start: u16| value.saturating_sub(start).min(255) as u8;
+                Color::from_rgb(channel(0), channel(255), channel(510))
+            }
+            Palette::Gradient(colors) => {
+                let position = value as usize * (colors.len() - 1);
+                let (index, t) = (position / 255, (position % 255) as u8);
+                match colors.get(index + 1) {
+                    Some(&next) => colors[index].lerp(next, t),
+                    None => colors[index],
+                }
+            }
+        }
+    }
+}
+
+// Counts time in fixed steps, for effects that change a frame at a time
+// rather than continuously.
+#[derive(Copy, Clone, Debug)]
+struct Ticker {
+    interval: u32,
+    elapsed: u32,
+}
+
+impl Ticker {
+    const fn new(interval: u32) -> Self {
+        Self {
+            interval,
+            elapsed: 0,
+        }
+    }
+
+    // How many steps have gone by.
+    fn advance(&mut self, dt: u32) -> u32 {
+        let interval = self.interval.max(1);
+        self.elapsed = self.elapsed.saturating_add(dt);
+        let steps = self.elapsed / interval;
+        self.elapsed %= interval;
+        steps
+    }
+}
+
+// Moves something along at a steady speed, a frame at a time, for effects that
+// move continuously. Whatever doesn't add up to a whole unit carries over to
+// the next frame, so slow speeds aren't rounded away.
+#[derive(Copy, Clone, Debug)]
+pub struct Drift {
+    ticker: Ticker,
+    remainder: u64,
+}
+
+impl Drift {
+    pub const fn new() -> Self {
+        Self {
+            ticker: Ticker::new(FRAME_US),
+            remainder: 0,
+        }
+    }
+
+    // How many frames have gone by, and how many units something moving
+    // `speed` units per second went in them.
+    pub fn advance(&mut self, dt: u32, speed: u64) -> (u32, u64) {
+        let steps = self.ticker.advance(dt);
+        let moved = speed * steps as u64 * FRAME_US as u64 + self.remainder;
+        self.remainder = moved % 1_000_000;
+        (steps, moved / 1_000_000)
+    }
+}
+
+impl Default for Drift {
+    fn default() -> Self {
+        Self::new()
+    }
+}
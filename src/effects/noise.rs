@@ -0,0 +1,61 @@
+use super::{math::noise, Drift, Palette};
+use crate::{display::Matrix, scene::Scene};
+
+// Smooth random blobs of color, drifting and changing shape. It's two layers
+// of value noise, a coarse one and a finer one, moving different ways.
+pub struct NoiseField {
+    palette: Palette,
+    scale: u16,
+    speed: u32,
+    seed: u32,
+    // How far the noise has moved, in 256ths of its own units, so slow
+    // speeds still move.
+    offset: u32,
+    drift: Drift,
+}
+
+impl NoiseField {
+    pub const fn new(palette: Palette, seed: u32) -> Self {
+        Self {
+            palette,
+            scale: 24,
+            speed: 128,
+            seed,
+            offset: 0,
+            drift: Drift::new(),
+        }
+    }
+
+    // How far through the noise each pixel goes, with 256 from one random
+    // value to the next. Bigger makes smaller blobs.
+    pub const fn with_scale(mut self, scale: u16) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    // How far the noise moves per second, in the same units as the scale.
+    pub const fn with_speed(mut self, speed: u32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+impl<const W: usize, const H: usize> Scene<Matrix<W, H>> for NoiseField {
+    fn update(&mut self, dt: u32) -> bool {
+        let (steps, moved) = self.drift.advance(dt, self.speed as u64 * 256);
+        self.offset = self.offset.wrapping_add(moved as u32);
+        steps > 0
+    }
+
+    fn render(&mut self, matrix: &mut Matrix<W, H>) {
+        let (scale, offset) = (self.scale as i32, (self.offset >> 8) as i32);
+        for (y, row) in matrix.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let (x, y) = (x as i32 * scale, y as i32 * scale);
+                let coarse = noise(x + offset, y + offset / 2, self.seed) as u32;
+                let fine = noise(2 * x - offset, 2 * y + offset, self.seed + 1) as u32;
+                *pixel = self.palette.color(((2 * coarse + fine) / 3) as u8);
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use super::{
+    math::{sin8, wave8},
+    Drift, Palette,
+};
+use crate::{display::Matrix, scene::Scene};
+
+// Overlapping sine waves, drifting over each other while the palette cycles.
+pub struct Plasma {
+    palette: Palette,
+    scale: u8,
+    speed: u32,
+    // In 256ths of an angle step, so slow speeds still move.
+    phase: u32,
+    drift: Drift,
+}
+
+impl Plasma {
+    pub const fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            scale: 8,
+            speed: 64,
+            phase: 0,
+            drift: Drift::new(),
+        }
+    }
+
+    // How far the waves go per pixel, out of 256 for a whole wave. Bigger is
+    // busier.
+    pub const fn with_scale(mut self, scale: u8) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    // How far the waves move per second, out of 256 for a whole wave.
+    pub const fn with_speed(mut self, speed: u32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+impl<const W: usize, const H: usize> Scene<Matrix<W, H>> for Plasma {
+    fn update(&mut self, dt: u32) -> bool {
+        let (steps, moved) = self.drift.advance(dt, self.speed as u64 * 256);
+        self.phase = self.phase.wrapping_add(moved as u32);
+        steps > 0
+    }
+
+    fn render(&mut self, matrix: &mut Matrix<W, H>) {
+        let phase = (self.phase >> 8) as u8;
+        let scale = self.scale as usize;
+        // Each wave moves at its own speed, so the pattern never repeats
+        // quite the same way.
+        let slow = (self.phase >> 9) as u8;
+        let fast = (self.phase.wrapping_mul(3) >> 9) as u8;
+        let (center_x, center_y) = (W / 2 + wave8(slow) as usize * W / 512, H / 2);
+
+        for (y, row) in matrix.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                // Near enough to the distance from the center, without a
+                // square root.
+                let (dx, dy) = (x.abs_diff(center_x), y.abs_diff(center_y));
+                let distance = dx.max(dy) + dx.min(dy) / 2;
+                let sum = sin8(((x * scale) as u8).wrapping_add(phase)) as i32
+                    + sin8(((y * scale) as u8).wrapping_sub(fast)) as i32
+                    + sin8((((x + y) * scale / 2) as u8).wrapping_add(slow)) as i32
+                    + sin8((distance * scale) as u8) as i32;
+                let value = ((sum + 508) * 255 / 1016) as u8;
+                *pixel = self.palette.color(value.wrapping_add(phase));
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use super::{math::Rng, Ticker, FRAME_US};
+use crate::{
+    display::{Color, Matrix},
+    scene::Scene,
+};
+
+#[derive(Copy, Clone, Default)]
+struct Drop {
+    // In 256ths of a pixel, and None while the column is waiting for a new
+    // drop.
+    y: Option<i32>,
+    // 256ths of a pixel per frame.
+    speed: i32,
+}
+
+// Streaks falling down the columns, like the digital rain in The Matrix. Each
+// one leaves a fading trail behind, with the odd pixel flickering in it like
+// changing characters.
+pub struct DigitalRain<const W: usize, const H: usize> {
+    drops: [Drop; W],
+    // How bright every pixel is, out of 255.
+    trails: [[u8; W]; H],
+    color: Color,
+    fade: u8,
+    density: u8,
+    speeds: (i32, i32),
+    rng: Rng,
+    ticker: Ticker,
+}
+
+impl<const W: usize, const H: usize> DigitalRain<W, H> {
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            drops: [Drop { y: None, speed: 0 }; W],
+            trails: [[0; W]; H],
+            color: Color::from_rgb(0, 255, 64),
+            fade: 230,
+            density: 4,
+            speeds: (64, 256),
+            rng: Rng::new(seed),
+            ticker: Ticker::new(FRAME_US),
+        }
+    }
+
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    // How much of its brightness a trail keeps each frame, out of 255. Bigger
+    // makes longer trails.
+    pub const fn with_fade(mut self, fade: u8) -> Self {
+        self.fade = fade;
+        self
+    }
+
+    // The chance of a new drop starting in an empty column each frame, out
+    // of 255.
+    pub const fn with_density(mut self, density: u8) -> Self {
+        self.density = density;
+        self
+    }
+
+    // The slowest and fastest drops, in 256ths of a pixel per frame.
+    pub const fn with_speeds(mut self, slowest: i32, fastest: i32) -> Self {
+        self.speeds = (slowest, fastest);
+        self
+    }
+
+    fn step(&mut self) {
+        for pixel in self.trails.iter_mut().flatten() {
+            *pixel = (*pixel as u16 * self.fade as u16 / 255) as u8;
+            if *pixel > 0 && self.rng.one_in(64) {
+                // Never as bright as a head.
+                *pixel = self.rng.byte().clamp(*pixel / 2, 254);
+            }
+        }
+
+        let (slowest, fastest) = self.speeds;
+        for x in 0..W {
+            let drop = &mut self.drops[x];
+            let Some(y) = drop.y else {
+                if self.rng.byte() < self.density {
+                    let spread = (fastest - slowest).max(0) as u32 + 1;
+                    *drop = Drop {
+                        y: Some(0),
+                        speed: slowest + self.rng.below(spread) as i32,
+                    };
+                }
+                continue;
+            };
+
+            // Lights up everything the head passed over since the last frame,
+            // so fast drops don't leave gaps.
+            let next = y + drop.speed;
+            for row in (y >> 8)..=(next >> 8) {
+                if let Some(pixel) = self.trails.get_mut(row as usize) {
+                    pixel[x] = 255;
+                }
+            }
+            drop.y = (next >> 8 < H as i32).then_some(next);
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Scene<Matrix<W, H>> for DigitalRain<W, H> {
+    fn update(&mut self, dt: u32) -> bool {
+        let steps = self.ticker.advance(dt);
+        for _ in 0..steps.min(H as u32) {
+            self.step();
+        }
+        steps > 0
+    }
+
+    fn render(&mut self, matrix: &mut Matrix<W, H>) {
+        for (row, trail) in matrix.iter_mut().zip(&self.trails) {
+            for (pixel, &brightness) in row.iter_mut().zip(trail) {
+                // The heads are nearly white.
+                *pixel = match brightness {
+                    255 => self.color.lerp(Color::white(), 160),
+                    _ => self.color.scale(brightness),
+                };
+            }
+        }
+    }
+}
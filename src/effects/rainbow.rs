@@ -0,0 +1,79 @@
+use super::{
+    math::{cos8, sin8},
+    Drift, Palette,
+};
+use crate::{display::Matrix, scene::Scene};
+
+// The whole spectrum in stripes, scrolling across the display.
+pub struct Rainbow {
+    palette: Palette,
+    angle: u8,
+    spread: u8,
+    speed: u32,
+    // In 256ths of a palette step.
+    phase: u32,
+    drift: Drift,
+}
+
+impl Rainbow {
+    pub const fn new() -> Self {
+        Self {
+            palette: Palette::Rainbow,
+            angle: 0,
+            spread: 4,
+            speed: 64,
+            phase: 0,
+            drift: Drift::new(),
+        }
+    }
+
+    pub const fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    // Which way the colors run, out of 256 for a whole turn. 0 runs them
+    // left to right, 64 top to bottom.
+    pub const fn with_angle(mut self, angle: u8) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    // How far through the palette each pixel goes, out of 256. Bigger makes
+    // narrower stripes.
+    pub const fn with_spread(mut self, spread: u8) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    // How far through the palette the colors scroll per second, out of 256.
+    pub const fn with_speed(mut self, speed: u32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+impl Default for Rainbow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize> Scene<Matrix<W, H>> for Rainbow {
+    fn update(&mut self, dt: u32) -> bool {
+        let (steps, moved) = self.drift.advance(dt, self.speed as u64 * 256);
+        self.phase = self.phase.wrapping_add(moved as u32);
+        steps > 0
+    }
+
+    fn render(&mut self, matrix: &mut Matrix<W, H>) {
+        let (dx, dy) = (cos8(self.angle) as i32, sin8(self.angle) as i32);
+        let (spread, phase) = (self.spread as i32, (self.phase >> 8) as i32);
+        for (y, row) in matrix.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let distance = (x as i32 * dx + y as i32 * dy) * spread / 127;
+                *pixel = self.palette.color((distance - phase) as u8);
+            }
+        }
+    }
+}
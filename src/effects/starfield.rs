@@ -0,0 +1,93 @@
+use super::{math::Rng, Drift};
+use crate::{
+    display::{Color, Matrix},
+    scene::Scene,
+};
+
+// How far away stars start out. Stars are placed across a square this many
+// units out on either side, which is exactly what's in view at this distance.
+const DEPTH: i32 = 1 << 16;
+
+#[derive(Copy, Clone, Default)]
+struct Star {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+// Flying forwards through N stars, which get brighter as they get closer.
+pub struct Starfield<const N: usize> {
+    stars: [Star; N],
+    color: Color,
+    speed: u32,
+    rng: Rng,
+    drift: Drift,
+}
+
+impl<const N: usize> Starfield<N> {
+    pub fn new(seed: u32) -> Self {
+        let mut starfield = Self {
+            stars: [Star::default(); N],
+            color: Color::white(),
+            speed: 1 << 15,
+            rng: Rng::new(seed),
+            drift: Drift::new(),
+        };
+        // Spread out through the whole depth to start with, rather than all
+        // coming in at once.
+        for i in 0..N {
+            let z = starfield.rng.below(DEPTH as u32) as i32 + 1;
+            starfield.stars[i] = starfield.spawn(z);
+        }
+        starfield
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    // How fast stars come in, in 65536ths of the starting distance per
+    // second.
+    pub fn with_speed(mut self, speed: u32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    fn spawn(&mut self, z: i32) -> Star {
+        let mut coordinate = || self.rng.below(2 * DEPTH as u32) as i32 - DEPTH;
+        Star {
+            x: coordinate(),
+            y: coordinate(),
+            z,
+        }
+    }
+}
+
+impl<const N: usize, const W: usize, const H: usize> Scene<Matrix<W, H>> for Starfield<N> {
+    fn update(&mut self, dt: u32) -> bool {
+        let (steps, moved) = self.drift.advance(dt, self.speed as u64);
+        for i in 0..N {
+            self.stars[i].z -= moved as i32;
+            if self.stars[i].z <= 0 {
+                self.stars[i] = self.spawn(DEPTH);
+            }
+        }
+        steps > 0
+    }
+
+    fn render(&mut self, matrix: &mut Matrix<W, H>) {
+        *matrix = [[Color::black(); W]; H];
+        // The view is square, so the longer side decides how far stars go.
+        let (center_x, center_y) = (W as i64 / 2, H as i64 / 2);
+        let half = center_x.max(center_y);
+        for star in &self.stars {
+            let x = center_x + star.x as i64 * half / star.z as i64;
+            let y = center_y + star.y as i64 * half / star.z as i64;
+            if (0..W as i64).contains(&x) && (0..H as i64).contains(&y) {
+                let brightness = 255 - (star.z as i64 * 255 / DEPTH as i64) as u8;
+                matrix[y as usize][x as usize] = self.color.scale(brightness);
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use embedded_graphics_core::{draw_target::DrawTarget, geometry::Size};
+
+use super::{put, Bytes, DrawError, ImageError, Viewport};
+use crate::display::Color;
+
+const BI_RGB: u32 = 0;
+const BI_BITFIELDS: u32 = 3;
+const BI_ALPHABITFIELDS: u32 = 6;
+
+// An uncompressed Windows bitmap: 1, 4 or 8 bit with a palette, or 16, 24 or
+// 32 bit color, optionally with bit fields. Pixels that are less than half
+// opaque, which only 32 bit bitmaps with an alpha mask can have, aren't drawn.
+#[derive(Copy, Clone)]
+pub struct Bmp<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    // Rows are usually stored bottom to top.
+    top_down: bool,
+    bits: u16,
+    pixels: usize,
+    // Bytes per row. Rows are padded to whole words.
+    stride: usize,
+    palette: &'a [u8],
+    // 4 for BITMAPINFOHEADER and later, 3 for the old BITMAPCOREHEADER.
+    palette_entry: usize,
+    // Red, green, blue and alpha masks for 16 and 32 bit bitmaps.
+    masks: [u32; 4],
+}
+
+impl<'a> Bmp<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, ImageError> {
+        let mut bytes = Bytes::new(data, 0);
+        if bytes.take(2)? != b"BM" {
+            return Err(ImageError::Invalid);
+        }
+        bytes.position = 10;
+        let pixels = bytes.u32_le()? as usize;
+        let header = bytes.u32_le()? as usize;
+
+        let (width, height, bits, compression, colors, palette_entry) = if header == 12 {
+            let width = bytes.u16_le()? as i32;
+            let height = bytes.u16_le()? as i32;
+            bytes.u16_le()?;
+            let bits = bytes.u16_le()?;
+            (width, height, bits, BI_RGB, 0, 3)
+        } else if header >= 40 {
+            let width = bytes.u32_le()? as i32;
+            let height = bytes.u32_le()? as i32;
+            bytes.u16_le()?;
+            let bits = bytes.u16_le()?;
+            let compression = bytes.u32_le()?;
+            bytes.position += 12;
+            let colors = bytes.u32_le()? as usize;
+            (width, height, bits, compression, colors, 4)
+        } else {
+            return Err(ImageError::Invalid);
+        };
+        if width <= 0 || height == 0 {
+            return Err(ImageError::Invalid);
+        }
+
+        // Bit fields come right after a BITMAPINFOHEADER, and are part of the
+        // header itself in later versions, which puts them in the same place.
+        let masks = match (compression, bits) {
+            (BI_RGB, 16) => [0x7c00, 0x03e0, 0x001f, 0],
+            (BI_RGB, 32) => [0xff0000, 0x00ff00, 0x0000ff, 0],
+            (BI_BITFIELDS | BI_ALPHABITFIELDS, 16 | 32) => {
+                let mut masks = Bytes::new(data, 14 + 40);
+                let mut fields = [masks.u32_le()?, masks.u32_le()?, masks.u32_le()?, 0];
+                if compression == BI_ALPHABITFIELDS || header >= 56 {
+                    fields[3] = masks.u32_le()?;
+                }
+                fields
+            }
+            (BI_RGB, 1 | 4 | 8 | 24) => [0; 4],
+            _ => return Err(ImageError::Unsupported),
+        };
+
+        let palette = if bits <= 8 {
+            let colors = if colors == 0 {
+                1 << bits
+            } else {
+                colors.min(1 << bits)
+            };
+            Bytes::new(data, header.saturating_add(14)).take(colors * palette_entry)?
+        } else {
+            &[]
+        };
+
+        // Sizes this big can't be real, and would overflow on a 32 bit chip.
+        let stride = (width as usize)
+            .checked_mul(bits as usize)
+            .ok_or(ImageError::Invalid)?
+            .div_ceil(32)
+            * 4;
+        let size = stride
+            .checked_mul(height.unsigned_abs() as usize)
+            .ok_or(ImageError::Invalid)?;
+        Bytes::new(data, pixels).take(size)?;
+
+        Ok(Self {
+            data,
+            width: width as u32,
+            height: height.unsigned_abs(),
+            top_down: height < 0,
+            bits,
+            pixels,
+            stride,
+            palette,
+            palette_entry,
+            masks,
+        })
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    // Everything is checked up front, so this only fails if the target does.
+    pub fn draw<D: DrawTarget>(
+        &self,
+        target: &mut D,
+        viewport: &Viewport,
+    ) -> Result<(), DrawError<D::Error>>
+    where
+        D::Color: From<Color>,
+    {
+        let stride = self.stride;
+        for stored in 0..self.height {
+            let y = if self.top_down {
+                stored
+            } else {
+                self.height - 1 - stored
+            };
+            let row = &self.data[self.pixels + stored as usize * stride..][..stride];
+            for x in 0..self.width {
+                if let Some(color) = self.pixel(row, x as usize) {
+                    put(target, viewport, x, y, color).map_err(DrawError::Target)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn pixel(&self, row: &[u8], x: usize) -> Option<Color> {
+        let value = match self.bits {
+            1 | 4 | 8 => {
+                let bits = self.bits as usize;
+                let shift = 8 - bits - x * bits % 8;
+                let index = (row[x * bits / 8] >> shift) as usize & ((1 << bits) - 1);
+                let entry = self.palette.get(index * self.palette_entry..)?.get(..3)?;
+                return Some(Color::from_rgb(entry[2], entry[1], entry[0]));
+            }
+            24 => return Some(Color::from_rgb(row[x * 3 + 2], row[x * 3 + 1], row[x * 3])),
+            16 => u16::from_le_bytes([row[x * 2], row[x * 2 + 1]]) as u32,
+            _ => u32::from_le_bytes(row[x * 4..x * 4 + 4].try_into().unwrap()),
+        };
+
+        let [r, g, b, alpha] = self.masks.map(|mask| field(value, mask));
+        (self.masks[3] == 0 || alpha >= 128).then_some(Color::from_rgb(r, g, b))
+    }
+}
+
+// Pulls a bit field out of a pixel, scaled to 8 bits.
+fn field(value: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let max = mask >> mask.trailing_zeros();
+    (((value & mask) >> mask.trailing_zeros()) as u64 * 255 / max as u64) as u8
+}
@@ -0,0 +1,497 @@
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    primitives::Rectangle,
+};
+
+use super::{Bytes, ImageError, Viewport};
+use crate::display::{Color, Frame, Matrix};
+
+const EXTENSION: u8 = 0x21;
+const IMAGE: u8 = 0x2c;
+const TRAILER: u8 = 0x3b;
+const GRAPHIC_CONTROL: u8 = 0xf9;
+const APPLICATION: u8 = 0xff;
+
+const MAX_CODES: usize = 4096;
+
+// A GIF, possibly animated. Parsing goes over the whole file once to check it
+// and count the frames, but doesn't decode them; that's up to a GifPlayer.
+#[derive(Copy, Clone)]
+pub struct Gif<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    palette: &'a [u8],
+    // Where the first block after the header is.
+    blocks: usize,
+    frames: usize,
+    loops: Option<u16>,
+}
+
+impl<'a> Gif<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, ImageError> {
+        let mut bytes = Bytes::new(data, 0);
+        if !matches!(bytes.take(6)?, b"GIF87a" | b"GIF89a") {
+            return Err(ImageError::Invalid);
+        }
+        let width = bytes.u16_le()? as u32;
+        let height = bytes.u16_le()? as u32;
+        let flags = bytes.u8()?;
+        bytes.take(2)?;
+        let palette = palette(&mut bytes, flags)?.unwrap_or(&[]);
+
+        let mut gif = Self {
+            data,
+            width,
+            height,
+            palette,
+            blocks: bytes.position,
+            frames: 0,
+            loops: Some(0),
+        };
+        let mut position = gif.blocks;
+        loop {
+            let (block, next) = gif.block(position)?;
+            match block {
+                Block::Image(_) => gif.frames += 1,
+                Block::Loops(loops) => gif.loops = (loops > 0).then_some(loops),
+                Block::End => break,
+                Block::Control(_) | Block::Other => {}
+            }
+            position = next;
+        }
+        Ok(gif)
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    // How many more times the animation plays after the first time through,
+    // or None if it loops forever.
+    pub fn loops(&self) -> Option<u16> {
+        self.loops
+    }
+
+    // Reads the block at `position` and returns it with the position of the
+    // next one.
+    fn block(&self, position: usize) -> Result<(Block<'a>, usize), ImageError> {
+        let mut bytes = Bytes::new(self.data, position);
+        let block = match bytes.u8()? {
+            EXTENSION => {
+                let label = bytes.u8()?;
+                let first = sub_block(&mut bytes)?;
+                let block = match (label, first) {
+                    (GRAPHIC_CONTROL, Some(&[flags, delay_low, delay_high, transparent, ..])) => {
+                        Block::Control(Control {
+                            disposal: match (flags >> 2) & 7 {
+                                2 => Disposal::Background,
+                                3 => Disposal::Previous,
+                                _ => Disposal::Keep,
+                            },
+                            delay: u16::from_le_bytes([delay_low, delay_high]),
+                            transparent: (flags & 1 > 0).then_some(transparent),
+                        })
+                    }
+                    (APPLICATION, Some(b"NETSCAPE2.0")) => match sub_block(&mut bytes)? {
+                        Some(&[1, loops_low, loops_high, ..]) => {
+                            Block::Loops(u16::from_le_bytes([loops_low, loops_high]))
+                        }
+                        Some(_) => Block::Other,
+                        None => return Ok((Block::Other, bytes.position)),
+                    },
+                    _ => Block::Other,
+                };
+                if first.is_some() {
+                    skip_sub_blocks(&mut bytes)?;
+                }
+                block
+            }
+            IMAGE => {
+                let left = bytes.u16_le()? as i32;
+                let top = bytes.u16_le()? as i32;
+                let width = bytes.u16_le()? as u32;
+                let height = bytes.u16_le()? as u32;
+                let flags = bytes.u8()?;
+                let palette = palette(&mut bytes, flags)?.unwrap_or(self.palette);
+                let data = bytes.position;
+                bytes.u8()?;
+                skip_sub_blocks(&mut bytes)?;
+                Block::Image(Image {
+                    area: Rectangle::new(Point::new(left, top), Size::new(width, height)),
+                    interlaced: flags & 0x40 > 0,
+                    palette,
+                    data,
+                })
+            }
+            TRAILER => Block::End,
+            _ => return Err(ImageError::Invalid),
+        };
+        Ok((block, bytes.position))
+    }
+}
+
+enum Block<'a> {
+    Control(Control),
+    Loops(u16),
+    Image(Image<'a>),
+    End,
+    Other,
+}
+
+// What to do with a frame once the next one is due.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+enum Disposal {
+    #[default]
+    Keep,
+    // Cleared to black, rather than the background color, like browsers do.
+    Background,
+    // Put back the way it was before the frame was drawn.
+    Previous,
+}
+
+// Applies to the image that follows it.
+#[derive(Copy, Clone, Default)]
+struct Control {
+    disposal: Disposal,
+    // In hundredths of a second.
+    delay: u16,
+    transparent: Option<u8>,
+}
+
+struct Image<'a> {
+    area: Rectangle,
+    interlaced: bool,
+    palette: &'a [u8],
+    // Where the LZW data starts, with the minimum code size.
+    data: usize,
+}
+
+// Reads a length prefixed sub-block, or None for the empty one at the end.
+fn sub_block<'a>(bytes: &mut Bytes<'a>) -> Result<Option<&'a [u8]>, ImageError> {
+    let length = bytes.u8()? as usize;
+    Ok((length > 0).then_some(bytes.take(length)?))
+}
+
+fn skip_sub_blocks(bytes: &mut Bytes) -> Result<(), ImageError> {
+    while sub_block(bytes)?.is_some() {}
+    Ok(())
+}
+
+fn palette<'a>(bytes: &mut Bytes<'a>, flags: u8) -> Result<Option<&'a [u8]>, ImageError> {
+    if flags & 0x80 == 0 {
+        return Ok(None);
+    }
+    Ok(Some(bytes.take(3 << ((flags & 7) + 1))?))
+}
+
+// Plays a GIF into a frame of its own rather than straight into the frame
+// buffer, since frames build on each other. It can be copied into a back
+// buffer whenever it changes. Besides the frame, this holds a copy of it for
+// frames that get restored after they're shown, and 16 KiB of decompression
+// tables. That's about 48 KiB for 64x64, so it's best kept in a static.
+pub struct GifPlayer<'a, const W: usize, const H: usize> {
+    gif: Gif<'a>,
+    viewport: Viewport,
+    screen: Matrix<W, H>,
+    previous: Matrix<W, H>,
+    lzw: Lzw,
+    // The next block to read.
+    position: usize,
+    // How to get rid of the frame being shown, and where it is on screen.
+    shown: Option<(Disposal, Rectangle)>,
+    // Microseconds the frame being shown stays up for, and has been up for.
+    delay: u32,
+    elapsed: u32,
+    loops: Option<u16>,
+    finished: bool,
+}
+
+impl<'a, const W: usize, const H: usize> GifPlayer<'a, W, H> {
+    pub fn new(gif: Gif<'a>, viewport: Viewport) -> Self {
+        Self {
+            gif,
+            viewport,
+            screen: Matrix::BLANK,
+            previous: Matrix::BLANK,
+            lzw: Lzw::new(),
+            position: gif.blocks,
+            shown: None,
+            delay: 0,
+            elapsed: 0,
+            loops: gif.loops,
+            finished: gif.frames == 0,
+        }
+    }
+
+    pub fn frame(&self) -> &Matrix<W, H> {
+        &self.screen
+    }
+
+    // Whether the last loop has been played. The last frame stays up.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn restart(&mut self) {
+        *self = Self::new(self.gif, self.viewport);
+    }
+
+    // Moves playback along by `micros` microseconds, skipping frames if it
+    // has to, and returns whether the picture changed. The first call shows
+    // the first frame.
+    pub fn update(&mut self, micros: u32) -> Result<bool, ImageError> {
+        self.elapsed = self.elapsed.saturating_add(micros);
+        let mut changed = false;
+        while !self.finished && self.elapsed >= self.delay {
+            self.elapsed -= self.delay;
+            changed |= self.next_frame()?;
+        }
+        Ok(changed)
+    }
+
+    fn next_frame(&mut self) -> Result<bool, ImageError> {
+        let mut control = Control::default();
+        loop {
+            let (block, next) = self.gif.block(self.position)?;
+            self.position = next;
+            match block {
+                Block::Control(next) => control = next,
+                Block::Image(image) => {
+                    self.show(&image, control)?;
+                    return Ok(true);
+                }
+                Block::End => {
+                    match self.loops {
+                        Some(0) => {
+                            self.finished = true;
+                            return Ok(false);
+                        }
+                        Some(loops) => self.loops = Some(loops - 1),
+                        None => {}
+                    }
+                    self.position = self.gif.blocks;
+                }
+                Block::Loops(_) | Block::Other => {}
+            }
+        }
+    }
+
+    fn show(&mut self, image: &Image, control: Control) -> Result<(), ImageError> {
+        match self.shown.take() {
+            Some((Disposal::Background, area)) => fill(&mut self.screen, area, Color::black()),
+            Some((Disposal::Previous, _)) => self.screen = self.previous,
+            _ => {}
+        }
+        if control.disposal == Disposal::Previous {
+            self.previous = self.screen;
+        }
+
+        let Self {
+            gif,
+            screen,
+            viewport,
+            lzw,
+            ..
+        } = self;
+        let area = image.area;
+        let mut pixel = 0;
+        lzw.decode(&gif.data[image.data..], |index| {
+            let (x, row) = (pixel % area.size.width, pixel / area.size.width);
+            pixel += 1;
+            if row >= area.size.height || Some(index) == control.transparent {
+                return;
+            }
+            let y = if image.interlaced {
+                interlaced_row(row, area.size.height)
+            } else {
+                row
+            };
+
+            let Some(rgb) = image
+                .palette
+                .get(index as usize * 3..index as usize * 3 + 3)
+            else {
+                return;
+            };
+            let (x, y) = (area.top_left.x as u32 + x, area.top_left.y as u32 + y);
+            if let Some(target) = viewport.map(x, y) {
+                fill(screen, target, Color::from_rgb(rgb[0], rgb[1], rgb[2]));
+            }
+        })?;
+
+        self.shown = Some((control.disposal, self.viewport.map_area(area)));
+        // Like browsers, treat very short delays as a sign the GIF wasn't made
+        // with them in mind.
+        self.delay = match control.delay {
+            0 | 1 => 100_000,
+            delay => delay as u32 * 10_000,
+        };
+        Ok(())
+    }
+}
+
+fn fill<const W: usize, const H: usize>(screen: &mut Matrix<W, H>, area: Rectangle, color: Color) {
+    for y in area.rows() {
+        for x in area.columns() {
+            if (0..W as i32).contains(&x) && (0..H as i32).contains(&y) {
+                screen[y as usize][x as usize] = color;
+            }
+        }
+    }
+}
+
+// Interlaced images store every 8th row starting from 0, then every 8th from
+// 4, every 4th from 2 and finally every 2nd from 1.
+fn interlaced_row(mut row: u32, height: u32) -> u32 {
+    for (start, step) in [(0, 8), (4, 8), (2, 4), (1, 2)] {
+        let rows = if height > start {
+            (height - start).div_ceil(step)
+        } else {
+            0
+        };
+        if row < rows {
+            return start + row * step;
+        }
+        row -= rows;
+    }
+    row
+}
+
+// The tables for decompressing LZW codes. Every code past the roots stands
+// for an earlier code's string with one more byte on the end.
+struct Lzw {
+    prefix: [u16; MAX_CODES],
+    suffix: [u8; MAX_CODES],
+    // A string gets read back to front, so it's collected here first.
+    stack: [u8; MAX_CODES],
+}
+
+impl Lzw {
+    fn new() -> Self {
+        Self {
+            prefix: [0; MAX_CODES],
+            suffix: [0; MAX_CODES],
+            stack: [0; MAX_CODES],
+        }
+    }
+
+    // Decodes an image's data, starting from the minimum code size, and hands
+    // each palette index to `emit`.
+    fn decode(&mut self, data: &[u8], mut emit: impl FnMut(u8)) -> Result<(), ImageError> {
+        let mut codes = Codes::new(data);
+        let min_size = codes.bytes.u8()? as u32;
+        if !(1..12).contains(&min_size) {
+            return Err(ImageError::Invalid);
+        }
+        let clear = 1 << min_size;
+        let end = clear + 1;
+        for root in 0..clear {
+            self.suffix[root as usize] = root as u8;
+        }
+
+        let mut size = min_size + 1;
+        let mut next = end + 1;
+        let mut previous = None;
+        while let Some(code) = codes.read(size)? {
+            if code == clear {
+                size = min_size + 1;
+                next = end + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                break;
+            }
+
+            let Some(previous_code) = previous else {
+                if code > clear {
+                    return Err(ImageError::Invalid);
+                }
+                emit(code as u8);
+                previous = Some(code);
+                continue;
+            };
+            let first = if code < next {
+                self.emit(code, clear, &mut emit)
+            } else if code == next {
+                let first = self.emit(previous_code, clear, &mut emit);
+                emit(first);
+                first
+            } else {
+                return Err(ImageError::Invalid);
+            };
+
+            if (next as usize) < MAX_CODES {
+                self.prefix[next as usize] = previous_code;
+                self.suffix[next as usize] = first;
+                next += 1;
+                if next == 1 << size && size < 12 {
+                    size += 1;
+                }
+            }
+            previous = Some(code);
+        }
+        Ok(())
+    }
+
+    // Emits the string for a code and returns its first byte.
+    fn emit(&mut self, mut code: u16, clear: u16, emit: &mut impl FnMut(u8)) -> u8 {
+        let mut length = 0;
+        while code > clear {
+            self.stack[length] = self.suffix[code as usize];
+            length += 1;
+            code = self.prefix[code as usize];
+        }
+        let first = self.suffix[code as usize];
+        emit(first);
+        for &byte in self.stack[..length].iter().rev() {
+            emit(byte);
+        }
+        first
+    }
+}
+
+// Reads variable size codes, least significant bit first, from the
+// sub-blocks of an image.
+struct Codes<'a> {
+    bytes: Bytes<'a>,
+    // Bytes left in the current sub-block.
+    left: usize,
+    bits: u32,
+    count: u32,
+}
+
+impl<'a> Codes<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            bytes: Bytes::new(data, 0),
+            left: 0,
+            bits: 0,
+            count: 0,
+        }
+    }
+
+    // None once the sub-blocks run out.
+    fn read(&mut self, size: u32) -> Result<Option<u16>, ImageError> {
+        while self.count < size {
+            if self.left == 0 {
+                self.left = self.bytes.u8()? as usize;
+                if self.left == 0 {
+                    return Ok(None);
+                }
+            }
+            self.bits |= (self.bytes.u8()? as u32) << self.count;
+            self.count += 8;
+            self.left -= 1;
+        }
+        let code = (self.bits & ((1 << size) - 1)) as u16;
+        self.bits >>= size;
+        self.count -= size;
+        Ok(Some(code))
+    }
+}
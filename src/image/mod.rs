@@ -0,0 +1,221 @@
+// Decoders for BMP, QOI and GIF images, straight from bytes in flash. Nothing
+// is decoded up front: pixels are drawn as they come out, scaled and cropped
+// on the way by a Viewport.
+
+mod bmp;
+mod gif;
+mod qoi;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    primitives::Rectangle,
+};
+
+pub use bmp::Bmp;
+pub use gif::{Gif, GifPlayer};
+pub use qoi::Qoi;
+
+use crate::display::Color;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum ImageError {
+    // Not an image of the expected format at all.
+    Invalid,
+    // Ends before it should.
+    Truncated,
+    // A valid image, using a feature that isn't supported, e.g. compressed
+    // BMPs.
+    Unsupported,
+}
+
+// What went wrong drawing an image that's only checked as it's decoded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DrawError<E> {
+    Image(ImageError),
+    Target(E),
+}
+
+impl<E> From<ImageError> for DrawError<E> {
+    fn from(error: ImageError) -> Self {
+        DrawError::Image(error)
+    }
+}
+
+// Which part of an image is drawn, and where it goes. The source part is
+// stretched over the target area, with nearest neighbor scaling.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub source: Rectangle,
+    pub target: Rectangle,
+}
+
+impl Viewport {
+    // The whole image, unscaled, with its top left corner at `position`.
+    pub fn at(size: Size, position: Point) -> Self {
+        Self {
+            source: Rectangle::new(Point::zero(), size),
+            target: Rectangle::new(position, size),
+        }
+    }
+
+    // The whole image, stretched over the area.
+    pub fn stretch(size: Size, area: Rectangle) -> Self {
+        Self {
+            source: Rectangle::new(Point::zero(), size),
+            target: area,
+        }
+    }
+
+    // The whole image, as big as it fits in the area without changing its
+    // aspect ratio, centered.
+    pub fn fit(size: Size, area: Rectangle) -> Self {
+        let target = if wider(area.size, size) {
+            Size::new(
+                ratio(size.width, area.size.height, size.height),
+                area.size.height,
+            )
+        } else {
+            Size::new(
+                area.size.width,
+                ratio(size.height, area.size.width, size.width),
+            )
+        };
+        Self {
+            source: Rectangle::new(Point::zero(), size),
+            target: Rectangle::new(area.top_left + centered(area.size, target), target),
+        }
+    }
+
+    // The middle of the image, scaled to cover the whole area without
+    // changing its aspect ratio. Whatever sticks out is cropped.
+    pub fn fill(size: Size, area: Rectangle) -> Self {
+        let source = if wider(area.size, size) {
+            Size::new(
+                size.width,
+                ratio(area.size.height, size.width, area.size.width),
+            )
+        } else {
+            Size::new(
+                ratio(area.size.width, size.height, area.size.height),
+                size.height,
+            )
+        };
+        Self {
+            source: Rectangle::new(centered(size, source), source),
+            target: area,
+        }
+    }
+
+    // Where a pixel of the image ends up, if it's drawn at all. When scaling
+    // down some pixels are skipped.
+    pub fn map(&self, x: u32, y: u32) -> Option<Rectangle> {
+        let point = Point::new(x as i32, y as i32);
+        if !self.source.contains(point) {
+            return None;
+        }
+        let area = self.map_area(Rectangle::new(point, Size::new(1, 1)));
+        (!area.is_zero_sized()).then_some(area)
+    }
+
+    // Where a part of the image ends up, cut down to the target area.
+    pub fn map_area(&self, area: Rectangle) -> Rectangle {
+        let (source, target) = (self.source, self.target);
+        let (left, right) = scale(
+            (source.top_left.x, source.size.width),
+            (target.top_left.x, target.size.width),
+            (area.top_left.x, area.size.width),
+        );
+        let (top, bottom) = scale(
+            (source.top_left.y, source.size.height),
+            (target.top_left.y, target.size.height),
+            (area.top_left.y, area.size.height),
+        );
+        Rectangle::new(
+            Point::new(left, top),
+            Size::new((right - left) as u32, (bottom - top) as u32),
+        )
+        .intersection(&target)
+    }
+}
+
+// Whether a has a wider aspect ratio than b.
+fn wider(a: Size, b: Size) -> bool {
+    a.width as u64 * b.height as u64 > b.width as u64 * a.height as u64
+}
+
+fn ratio(value: u32, numerator: u32, denominator: u32) -> u32 {
+    (value as u64 * numerator as u64 / denominator.max(1) as u64) as u32
+}
+
+fn centered(outer: Size, inner: Size) -> Point {
+    Point::new(
+        (outer.width as i32 - inner.width as i32) / 2,
+        (outer.height as i32 - inner.height as i32) / 2,
+    )
+}
+
+// Maps a span of one coordinate, given as a start and a length, from the
+// source span to the target span. Returns the start and end of the result.
+fn scale(source: (i32, u32), target: (i32, u32), span: (i32, u32)) -> (i32, i32) {
+    let map = |value: i32| {
+        let offset = (value - source.0) as i64 * target.1 as i64;
+        (target.0 as i64 + offset.div_euclid(source.1.max(1) as i64)) as i32
+    };
+    let start = map(span.0);
+    (start, map(span.0 + span.1 as i32).max(start))
+}
+
+// Draws one pixel of an image through a viewport.
+fn put<D: DrawTarget>(
+    target: &mut D,
+    viewport: &Viewport,
+    x: u32,
+    y: u32,
+    color: Color,
+) -> Result<(), D::Error>
+where
+    D::Color: From<Color>,
+{
+    match viewport.map(x, y) {
+        Some(area) => target.fill_solid(&area, color.into()),
+        None => Ok(()),
+    }
+}
+
+// Reads through an image's bytes, failing with Truncated at the end.
+struct Bytes<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Bytes<'a> {
+    fn new(data: &'a [u8], position: usize) -> Self {
+        Self { data, position }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], ImageError> {
+        let end = self.position.checked_add(length);
+        let bytes = end
+            .and_then(|end| self.data.get(self.position..end))
+            .ok_or(ImageError::Truncated)?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, ImageError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16_le(&mut self) -> Result<u16, ImageError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32_le(&mut self) -> Result<u32, ImageError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u32_be(&mut self) -> Result<u32, ImageError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
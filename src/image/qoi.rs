@@ -0,0 +1,106 @@
+use embedded_graphics_core::{draw_target::DrawTarget, geometry::Size};
+
+use super::{put, Bytes, DrawError, ImageError, Viewport};
+use crate::display::Color;
+
+const QOI_OP_RGB: u8 = 0xfe;
+const QOI_OP_RGBA: u8 = 0xff;
+const QOI_OP_INDEX: u8 = 0x00;
+const QOI_OP_DIFF: u8 = 0x40;
+const QOI_OP_LUMA: u8 = 0x80;
+const QOI_OP_RUN: u8 = 0xc0;
+
+// A "Quite OK Image". Compresses about as well as PNG, but decodes in a single
+// pass with 256 bytes of state. Pixels that are less than half opaque aren't
+// drawn.
+#[derive(Copy, Clone)]
+pub struct Qoi<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+}
+
+impl<'a> Qoi<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, ImageError> {
+        let mut bytes = Bytes::new(data, 0);
+        if bytes.take(4)? != b"qoif" {
+            return Err(ImageError::Invalid);
+        }
+        let width = bytes.u32_be()?;
+        let height = bytes.u32_be()?;
+        Ok(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    // Stops with Truncated if the data runs out early, after drawing what it
+    // could.
+    pub fn draw<D: DrawTarget>(
+        &self,
+        target: &mut D,
+        viewport: &Viewport,
+    ) -> Result<(), DrawError<D::Error>>
+    where
+        D::Color: From<Color>,
+    {
+        let mut bytes = Bytes::new(self.data, 14);
+        let mut index = [[0; 4]; 64];
+        let mut pixel = [0, 0, 0, 255];
+        let mut run = 0;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if run > 0 {
+                    run -= 1;
+                } else {
+                    let op = bytes.u8()?;
+                    match op {
+                        QOI_OP_RGB => pixel[..3].copy_from_slice(bytes.take(3)?),
+                        QOI_OP_RGBA => pixel.copy_from_slice(bytes.take(4)?),
+                        _ => match op & 0xc0 {
+                            QOI_OP_INDEX => pixel = index[op as usize],
+                            QOI_OP_DIFF => {
+                                pixel[0] = pixel[0].wrapping_add((op >> 4) & 3).wrapping_sub(2);
+                                pixel[1] = pixel[1].wrapping_add((op >> 2) & 3).wrapping_sub(2);
+                                pixel[2] = pixel[2].wrapping_add(op & 3).wrapping_sub(2);
+                            }
+                            QOI_OP_LUMA => {
+                                let next = bytes.u8()?;
+                                let green = (op & 0x3f).wrapping_sub(32);
+                                pixel[0] = pixel[0]
+                                    .wrapping_add(green)
+                                    .wrapping_add(next >> 4)
+                                    .wrapping_sub(8);
+                                pixel[1] = pixel[1].wrapping_add(green);
+                                pixel[2] = pixel[2]
+                                    .wrapping_add(green)
+                                    .wrapping_add(next & 0xf)
+                                    .wrapping_sub(8);
+                            }
+                            QOI_OP_RUN => run = op & 0x3f,
+                            _ => unreachable!(),
+                        },
+                    }
+                    index[hash(pixel)] = pixel;
+                }
+
+                let [r, g, b, alpha] = pixel;
+                if alpha >= 128 {
+                    put(target, viewport, x, y, Color::from_rgb(r, g, b))
+                        .map_err(DrawError::Target)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn hash([r, g, b, a]: [u8; 4]) -> usize {
+    (r as usize * 3 + g as usize * 5 + b as usize * 7 + a as usize * 11) % 64
+}
@@ -1,9 +1,16 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(incomplete_features)]
 #![feature(type_alias_impl_trait, concat_bytes, async_fn_in_trait, trait_alias)]
 
 // pub mod legacy_pin;
 // pub mod net;
 // pub mod time_driver;
+pub mod assets;
 pub mod display;
-pub mod dvd_logo;
+pub mod effects;
+pub mod image;
+pub mod motion;
+pub mod scene;
+pub mod sprite;
+pub mod text;
+pub mod transition;
@@ -4,17 +4,22 @@
 
 use core::mem::transmute;
 
+use board::{
+    assets, display,
+    motion::{fixed, Body, Bounce, Bouncer},
+    scene::Runner,
+};
 use cortex_m::delay::Delay;
-// use board::{display, dvd_logo};
 use defmt::println;
 use defmt_rtt as _;
-// use fugit::TimerDurationU64;
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    primitives::Rectangle,
+};
 use panic_probe as _;
-// use rp2040_hal::timer::Instant;
 use rp2040_hal::{clocks, entry, gpio, pac, Sio, Timer, Watchdog};
 
 const XOSC_FREQ_HZ: u32 = 12_000_000;
-// const TIMER_HZ: u32 = 1_000_000;
 
 #[embassy_executor::task]
 async fn main(_spawner: embassy_executor::Spawner) {
@@ -43,52 +48,12 @@ async fn main(_spawner: embassy_executor::Spawner) {
     .ok()
     .unwrap();
 
-    let _timer = Timer::new(pac.TIMER, &mut pac.RESETS);
-
-    // Display rendering function.
-    const LOGO_REFRESH_MS: u64 = 60;
-    const LOGO_REFRESH_DURATION: TimerDurationU64<TIMER_HZ> =
-        TimerDurationU64::from_ticks(LOGO_REFRESH_MS * 1000);
-    let colors = [
-        0xffffff, 0xff0000, 0x00ff00, 0x0000ff, 0xffff00, 0x00ffff, 0xff00ff,
-    ].map(display::Color::from_hex);
-    let mut last_update = Instant::from_ticks(0);
-    let (mut x, mut y) = (0, 10);
-    let (mut dx, mut dy) = (1_isize, 1_isize);
-    let mut color_index = 0;
-
-    let render = |matrix: &mut display::Matrix| {
-        if timer.get_counter() - last_update >= LOGO_REFRESH_DURATION {
-            last_update = timer.get_counter();
-            x = (x as isize + dx) as usize;
-            y = (y as isize + dy) as usize;
-
-            *matrix = [[display::Color::black(); display::spec::VIRTUAL_WIDTH]; display::spec::VIRTUAL_HEIGHT];
-            let dvd_logo = dvd_logo::make_dvd_logo(colors[color_index % colors.len()]);
-            for (dvd_y, row) in dvd_logo.iter().enumerate() {
-                for (dvd_x, cell) in row.iter().enumerate() {
-                    matrix[dvd_y + y][dvd_x + x] = *cell;
-                }
-            }
-
-            let mut bounced = false;
-            if x == 0 || x == display::spec::VIRTUAL_WIDTH - dvd_logo::WIDTH {
-                dx = -dx;
-                bounced = true;
-            }
-            if y == 0 || y == display::spec::VIRTUAL_HEIGHT - dvd_logo::HEIGHT {
-                dy = -dy;
-                bounced = true;
-            }
-            if bounced {
-                color_index += 1;
-            }
-        }
-    };
+    let timer = Timer::new(pac.TIMER, &mut pac.RESETS);
 
     // Initialize display and run draw loop.
-    let mut display = display::Driver::init(
+    let mut display: display::Driver = display::Driver::init(
         &timer,
+        display::spec::TWO_PANEL_64X64,
         pins.gpio2,
         pins.gpio3,
         pins.gpio4,
@@ -103,7 +68,34 @@ async fn main(_spawner: embassy_executor::Spawner) {
         pins.gpio13,
         pins.gpio14,
     );
-    display.draw_loop(render);
+    // Bounces the DVD logo around diagonally, changing color whenever it hits
+    // an edge. Steps far enough around the color wheel that consecutive
+    // bounces are easy to tell apart.
+    let area = Rectangle::new(
+        Point::zero(),
+        Size::new(
+            display::spec::VIRTUAL_WIDTH as u32,
+            display::spec::VIRTUAL_HEIGHT as u32,
+        ),
+    );
+    let logo = Body::new(
+        assets::DVD_LOGO,
+        Point::new(0, 10),
+        fixed(50) / 3,
+        fixed(50) / 3,
+    )
+    .with_color(display::Color::from_hsv(0, 255, 255));
+    let mut hue = 0;
+    let bouncer = Bouncer::new(area, [logo], |logo, bounce| {
+        hue = (hue + 137) % 360;
+        logo.color = display::Color::from_hsv(hue, 255, 255);
+        if let Bounce::Corner(..) = bounce {
+            println!("Corner!");
+        }
+    });
+
+    let mut runner = Runner::new(bouncer, &timer);
+    display.draw_loop(|matrix| runner.render(matrix));
 }
 
 #[link_section = ".boot2"]
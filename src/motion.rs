@@ -0,0 +1,295 @@
+// Sprites bouncing around an area, off its edges and off each other, like the
+// DVD logo. Positions and velocities are 16.16 fixed point, so bodies can move
+// slower than a pixel per update, which is most of them at typical refresh
+// rates.
+
+use embedded_graphics_core::{draw_target::DrawTarget, geometry::Point, primitives::Rectangle};
+
+use crate::{
+    display::{Canvas, Color, Matrix},
+    scene::Scene,
+    sprite::Sprite,
+};
+
+pub const ONE: i32 = 1 << 16;
+
+// Whole pixels, or pixels per second, in fixed point. Divide for fractions,
+// e.g. `fixed(50) / 3`.
+pub const fn fixed(pixels: i32) -> i32 {
+    pixels * ONE
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Bounce {
+    // Off one edge of the area.
+    Wall(Side),
+    // Off an edge while touching another one: right into a corner. The left
+    // or right side comes first, then the top or bottom.
+    Corner(Side, Side),
+    // Off another body, by its index.
+    Body(usize),
+}
+
+#[derive(Copy, Clone)]
+pub struct Body {
+    pub sprite: Sprite,
+    // The top left corner, in fixed point pixels.
+    pub x: i32,
+    pub y: i32,
+    // In fixed point pixels per second.
+    pub dx: i32,
+    pub dy: i32,
+    // For mask sprites.
+    pub color: Color,
+    // What's left over from moving, in millionths of a fixed point step, so
+    // slow bodies don't lose time to rounding.
+    remainder: (i32, i32),
+}
+
+impl Body {
+    pub fn new(sprite: Sprite, position: Point, dx: i32, dy: i32) -> Self {
+        Self {
+            sprite,
+            x: fixed(position.x),
+            y: fixed(position.y),
+            dx,
+            dy,
+            color: Color::white(),
+            remainder: (0, 0),
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    // Where the sprite is drawn, rounded to the nearest pixel.
+    pub fn position(&self) -> Point {
+        Point::new((self.x + ONE / 2) >> 16, (self.y + ONE / 2) >> 16)
+    }
+
+    fn width(&self) -> i32 {
+        fixed(self.sprite.width() as i32)
+    }
+
+    fn height(&self) -> i32 {
+        fixed(self.sprite.height() as i32)
+    }
+}
+
+// A number of bodies moving around an area. Every bounce is passed to a
+// callback along with the body that bounced, e.g. to change its color. When
+// two bodies hit each other, they each get a bounce.
+//
+// Collisions between bodies go by their bounding boxes, and bodies that hit
+// each other swap velocities along the way they hit, as if they all weighed
+// the same.
+pub struct Bouncer<const N: usize, B> {
+    pub bodies: [Body; N],
+    area: Rectangle,
+    background: Color,
+    on_bounce: B,
+}
+
+impl<const N: usize, B: FnMut(&mut Body, Bounce)> Bouncer<N, B> {
+    // Bodies that start out partly or wholly outside the area are moved in.
+    pub fn new(area: Rectangle, mut bodies: [Body; N], on_bounce: B) -> Self {
+        for body in &mut bodies {
+            let (left, right, top, bottom) = limits(area, body);
+            body.x = body.x.clamp(left, right);
+            body.y = body.y.clamp(top, bottom);
+        }
+        Self {
+            bodies,
+            area,
+            background: Color::black(),
+            on_bounce,
+        }
+    }
+
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.background = background;
+        self
+    }
+
+    // Moves everything along by `dt` microseconds and returns whether any
+    // body moved to a different pixel or bounced.
+    pub fn step(&mut self, dt: u32) -> bool {
+        let before = self.bodies.map(|body| body.position());
+        let mut bounced = false;
+
+        for body in &mut self.bodies {
+            travel(&mut body.x, &mut body.remainder.0, body.dx, dt);
+            travel(&mut body.y, &mut body.remainder.1, body.dy, dt);
+            if let Some(bounce) = bounce_off_walls(self.area, body) {
+                (self.on_bounce)(body, bounce);
+                bounced = true;
+            }
+        }
+
+        for i in 0..N {
+            let (left, right) = self.bodies.split_at_mut(i + 1);
+            let a = &mut left[i];
+            for (j, b) in right.iter_mut().enumerate() {
+                if collide(a, b) {
+                    (self.on_bounce)(a, Bounce::Body(i + 1 + j));
+                    (self.on_bounce)(b, Bounce::Body(i));
+                    bounced = true;
+                }
+            }
+        }
+
+        // Pushing bodies apart can push them out of the area, but they
+        // shouldn't bounce off the walls for it.
+        for body in &mut self.bodies {
+            let (left, right, top, bottom) = limits(self.area, body);
+            body.x = body.x.clamp(left, right);
+            body.y = body.y.clamp(top, bottom);
+        }
+
+        bounced
+            || self
+                .bodies
+                .iter()
+                .zip(before)
+                .any(|(body, before)| body.position() != before)
+    }
+
+    // Draws the bodies on top of whatever's already there, in order, so later
+    // ones end up on top.
+    pub fn draw<D: DrawTarget>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D::Color: From<Color>,
+    {
+        for body in &self.bodies {
+            body.sprite.draw(target, body.position(), body.color)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize, B, const W: usize, const H: usize> Scene<Matrix<W, H>> for Bouncer<N, B>
+where
+    B: FnMut(&mut Body, Bounce),
+{
+    fn update(&mut self, dt: u32) -> bool {
+        self.step(dt)
+    }
+
+    fn render(&mut self, matrix: &mut Matrix<W, H>) {
+        *matrix = [[self.background; W]; H];
+        self.draw(&mut Canvas::new(matrix)).unwrap();
+    }
+}
+
+// Moves a coordinate along at `speed` for `dt` microseconds.
+fn travel(position: &mut i32, remainder: &mut i32, speed: i32, dt: u32) {
+    let moved = speed as i64 * dt as i64 + *remainder as i64;
+    *remainder = moved.rem_euclid(1_000_000) as i32;
+    let steps = moved.div_euclid(1_000_000);
+    *position = position.saturating_add(steps.clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+}
+
+// How far a body's top left corner can go in each direction. A body too big to
+// fit is held against the top left.
+fn limits(area: Rectangle, body: &Body) -> (i32, i32, i32, i32) {
+    let (left, top) = (fixed(area.top_left.x), fixed(area.top_left.y));
+    let right = left + fixed(area.size.width as i32) - body.width();
+    let bottom = top + fixed(area.size.height as i32) - body.height();
+    (left, right.max(left), top, bottom.max(top))
+}
+
+// Reflects a body that's gone past an edge back inside.
+fn bounce_off_walls(area: Rectangle, body: &mut Body) -> Option<Bounce> {
+    let (left, right, top, bottom) = limits(area, body);
+
+    // Bodies that fill the area, or more, can't move at all.
+    let horizontal = if left == right {
+        body.x = left;
+        None
+    } else if body.x < left {
+        body.x = (2 * left - body.x).min(right);
+        body.dx = body.dx.abs();
+        Some(Side::Left)
+    } else if body.x > right {
+        body.x = (2 * right - body.x).max(left);
+        body.dx = -body.dx.abs();
+        Some(Side::Right)
+    } else {
+        None
+    };
+    let vertical = if top == bottom {
+        body.y = top;
+        None
+    } else if body.y < top {
+        body.y = (2 * top - body.y).min(bottom);
+        body.dy = body.dy.abs();
+        Some(Side::Top)
+    } else if body.y > bottom {
+        body.y = (2 * bottom - body.y).max(top);
+        body.dy = -body.dy.abs();
+        Some(Side::Bottom)
+    } else {
+        None
+    };
+
+    // It's a corner if the body is touching both edges on screen, even if it
+    // only bounced off one of them just now.
+    let position = body.position();
+    let touching_x = match position.x {
+        x if x == (left + ONE / 2) >> 16 => Some(Side::Left),
+        x if x == (right + ONE / 2) >> 16 => Some(Side::Right),
+        _ => None,
+    };
+    let touching_y = match position.y {
+        y if y == (top + ONE / 2) >> 16 => Some(Side::Top),
+        y if y == (bottom + ONE / 2) >> 16 => Some(Side::Bottom),
+        _ => None,
+    };
+
+    match (horizontal, vertical, touching_x, touching_y) {
+        (None, None, _, _) => None,
+        (_, _, Some(x), Some(y)) => Some(Bounce::Corner(x, y)),
+        (Some(side), _, _, _) | (None, Some(side), _, _) => Some(Bounce::Wall(side)),
+    }
+}
+
+// Separates two bodies that overlap and, if they're moving towards each other,
+// bounces them apart. Returns whether they bounced.
+fn collide(a: &mut Body, b: &mut Body) -> bool {
+    let overlap_x = (a.x + a.width()).min(b.x + b.width()) - a.x.max(b.x);
+    let overlap_y = (a.y + a.height()).min(b.y + b.height()) - a.y.max(b.y);
+    if overlap_x <= 0 || overlap_y <= 0 {
+        return false;
+    }
+
+    // They hit along whichever way they overlap least. Each one moves back
+    // half the overlap.
+    if overlap_x < overlap_y {
+        let (first, second) = if a.x < b.x { (a, b) } else { (b, a) };
+        first.x -= overlap_x / 2;
+        second.x += overlap_x - overlap_x / 2;
+        if first.dx > second.dx {
+            core::mem::swap(&mut first.dx, &mut second.dx);
+            return true;
+        }
+    } else {
+        let (first, second) = if a.y < b.y { (a, b) } else { (b, a) };
+        first.y -= overlap_y / 2;
+        second.y += overlap_y - overlap_y / 2;
+        if first.dy > second.dy {
+            core::mem::swap(&mut first.dy, &mut second.dy);
+            return true;
+        }
+    }
+    false
+}
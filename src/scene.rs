@@ -0,0 +1,286 @@
+// Scenes are what gets shown on the panels: anything that moves along with
+// time and can draw itself into a frame. A Runner turns one into a render
+// function for any of the drivers' draw loops, keeping track of time on the
+// way, and a Scheduler is a scene that switches between others.
+
+use crate::{display::Matrix, transition::Transition};
+
+// Everything is in microseconds, which is what the RP2040's timer counts.
+pub trait Clock {
+    fn now(&self) -> u64;
+}
+
+#[cfg(feature = "rp2040")]
+impl Clock for rp2040_hal::Timer {
+    fn now(&self) -> u64 {
+        self.get_counter().ticks()
+    }
+}
+
+// Counts from when the instant was taken.
+#[cfg(feature = "std")]
+impl Clock for std::time::Instant {
+    fn now(&self) -> u64 {
+        self.elapsed().as_micros() as u64
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> u64 {
+        (**self).now()
+    }
+}
+
+pub trait Scene<F> {
+    // Moves the scene along by `dt`, the time since the last update, and
+    // returns whether it needs to be rendered. Updates happen between every
+    // refresh, so most of them are very short.
+    fn update(&mut self, dt: u32) -> bool;
+
+    // Draws the scene into the back buffer. Unless the driver preserves the
+    // back buffer, whatever's there is from two frames ago, so this should
+    // draw the whole frame.
+    fn render(&mut self, frame: &mut F);
+
+    // Called whenever a scheduler switches to the scene, before its first
+    // update.
+    fn enter(&mut self) {}
+}
+
+impl<F, S: Scene<F> + ?Sized> Scene<F> for &mut S {
+    fn update(&mut self, dt: u32) -> bool {
+        (**self).update(dt)
+    }
+
+    fn render(&mut self, frame: &mut F) {
+        (**self).render(frame)
+    }
+
+    fn enter(&mut self) {
+        (**self).enter()
+    }
+}
+
+// Runs a scene against a clock. Pass `|frame| runner.render(frame)` to
+// draw_loop.
+pub struct Runner<S, C> {
+    scene: S,
+    clock: C,
+    last_update: Option<u64>,
+}
+
+impl<S, C: Clock> Runner<S, C> {
+    pub fn new(scene: S, clock: C) -> Self {
+        Self {
+            scene,
+            clock,
+            last_update: None,
+        }
+    }
+
+    // E.g. to send a scheduler to the next scene from the render function.
+    pub fn scene(&mut self) -> &mut S {
+        &mut self.scene
+    }
+
+    // Updates the scene with however much time has passed since the last
+    // call, renders it if it changed and returns whether it did.
+    pub fn render<F>(&mut self, frame: &mut F) -> bool
+    where
+        S: Scene<F>,
+    {
+        let now = self.clock.now();
+        let dt = match self.last_update {
+            Some(last_update) => (now - last_update).min(u32::MAX as u64) as u32,
+            None => 0,
+        };
+        self.last_update = Some(now);
+
+        let changed = self.scene.update(dt);
+        if changed {
+            self.scene.render(frame);
+        }
+        changed
+    }
+}
+
+// How often a transition is drawn, which doesn't have to keep up with the
+// refresh rate. 50 fps.
+const TRANSITION_FRAME_US: u32 = 20_000;
+
+// Shows one of a number of scenes at a time, moving on to the next one every
+// so often or when told to. Only the scene being shown is updated, except
+// during a transition, when the one on its way out keeps going too.
+pub struct Scheduler<'a, F> {
+    scenes: &'a mut [&'a mut dyn Scene<F>],
+    current: usize,
+    // How long each scene stays up, if they change on their own.
+    interval: Option<u32>,
+    shown_for: u32,
+    switched: bool,
+    // Whether anything has been shown yet, so there's something to transition
+    // from.
+    rendered: bool,
+    transition: Option<Transitioning<'a, F>>,
+}
+
+struct Transitioning<'a, F> {
+    transition: Transition,
+    // The outgoing and incoming scenes get rendered into these.
+    buffers: &'a mut [F; 2],
+    // Transition::draw for whatever F is, so the Scene impl below doesn't need
+    // to know.
+    draw: fn(&Transition, &F, &F, u32, u32, &mut F),
+    // The scene on its way out, while there's a transition going.
+    outgoing: Option<usize>,
+    elapsed: u32,
+    since_frame: u32,
+    // Changes every transition, so dissolves look different every time.
+    seed: u32,
+}
+
+impl<'a, F> Scheduler<'a, F> {
+    // Starts with the first scene. Panics if there aren't any.
+    pub fn new(scenes: &'a mut [&'a mut dyn Scene<F>]) -> Self {
+        assert!(!scenes.is_empty());
+        Self {
+            scenes,
+            current: 0,
+            interval: None,
+            shown_for: 0,
+            switched: true,
+            rendered: false,
+            transition: None,
+        }
+    }
+
+    // Moves on to the next scene every `interval` microseconds.
+    pub fn with_interval(mut self, interval: u32) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    // Switches to a scene at the next update, wrapping around past the last
+    // one, and starts its time over.
+    pub fn show(&mut self, index: usize) {
+        let previous = self.current;
+        self.current = index % self.scenes.len();
+        self.shown_for = 0;
+        self.switched = true;
+
+        if let Some(transition) = &mut self.transition {
+            // Starting over from whatever's being shown right now, even if
+            // that's halfway through another transition, would need a third
+            // buffer, so the old one just gets cut short.
+            if self.rendered && previous != self.current {
+                transition.outgoing = Some(previous);
+                transition.elapsed = 0;
+                transition.since_frame = 0;
+                transition.seed = transition.seed.wrapping_add(1);
+            } else {
+                transition.outgoing = None;
+            }
+        }
+    }
+
+    pub fn next(&mut self) {
+        self.show(self.current + 1);
+    }
+
+    pub fn previous(&mut self) {
+        self.show(self.current + self.scenes.len() - 1);
+    }
+}
+
+impl<'a, const W: usize, const H: usize> Scheduler<'a, Matrix<W, H>> {
+    // Goes from one scene to the next with a transition, rendering both into
+    // the buffers while it's going.
+    pub fn with_transition(
+        mut self,
+        transition: Transition,
+        buffers: &'a mut [Matrix<W, H>; 2],
+    ) -> Self {
+        self.transition = Some(Transitioning {
+            transition,
+            buffers,
+            draw: Transition::draw,
+            outgoing: None,
+            elapsed: 0,
+            since_frame: 0,
+            seed: 0,
+        });
+        self
+    }
+}
+
+impl<F> Scene<F> for Scheduler<'_, F> {
+    fn update(&mut self, dt: u32) -> bool {
+        if let Some(interval) = self.interval {
+            self.shown_for = self.shown_for.saturating_add(dt);
+            if self.shown_for >= interval {
+                self.next();
+            }
+        }
+
+        let switched = core::mem::take(&mut self.switched);
+        let scene = &mut self.scenes[self.current];
+        if switched {
+            scene.enter();
+        }
+        // A new scene is always rendered, so it never shows up on top of
+        // whatever was there before.
+        let changed = scene.update(dt) || switched;
+
+        let Some(transition) = &mut self.transition else {
+            return changed;
+        };
+        let Some(outgoing) = transition.outgoing else {
+            return changed;
+        };
+        // The update that switched scenes is the transition's first frame.
+        if !switched {
+            transition.elapsed = transition.elapsed.saturating_add(dt);
+        }
+        if transition.elapsed >= transition.transition.duration {
+            transition.outgoing = None;
+            return true;
+        }
+
+        // Both scenes keep moving underneath the transition.
+        if self.scenes[outgoing].update(dt) || switched {
+            self.scenes[outgoing].render(&mut transition.buffers[0]);
+        }
+        if changed {
+            self.scenes[self.current].render(&mut transition.buffers[1]);
+        }
+        transition.since_frame = transition.since_frame.saturating_add(dt);
+        if switched || transition.since_frame >= TRANSITION_FRAME_US {
+            transition.since_frame = 0;
+            return true;
+        }
+        false
+    }
+
+    fn render(&mut self, frame: &mut F) {
+        self.rendered = true;
+        match &self.transition {
+            Some(transition) if transition.outgoing.is_some() => (transition.draw)(
+                &transition.transition,
+                &transition.buffers[0],
+                &transition.buffers[1],
+                transition.elapsed,
+                transition.seed,
+                frame,
+            ),
+            _ => self.scenes[self.current].render(frame),
+        }
+    }
+
+    fn enter(&mut self) {
+        self.switched = true;
+    }
+}
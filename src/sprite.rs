@@ -0,0 +1,113 @@
+// Small images meant to live in flash and be drawn over whatever is already in
+// a frame. The ones in assets/ are generated from PNGs at build time; see
+// build.rs.
+
+use embedded_graphics_core::{draw_target::DrawTarget, geometry::Point, Pixel};
+
+use crate::display::Color;
+
+#[derive(Copy, Clone)]
+pub enum SpriteData {
+    // One bit per pixel, with every row starting on a new byte and the
+    // leftmost pixel in the top bit. Set pixels are drawn in whatever color
+    // the sprite is drawn with and the rest are transparent.
+    Mask(&'static [u8]),
+    // A byte per pixel, indexing into the palette.
+    Indexed {
+        palette: &'static [Color],
+        pixels: &'static [u8],
+        transparent: Option<u8>,
+    },
+    // Two bytes per pixel, the same as an Rgb565Frame. The mask, laid out
+    // like a Mask sprite, says which pixels are opaque; without one they all
+    // are.
+    Rgb565 {
+        pixels: &'static [u16],
+        mask: Option<&'static [u8]>,
+    },
+}
+
+#[derive(Copy, Clone)]
+pub struct Sprite {
+    width: usize,
+    height: usize,
+    data: SpriteData,
+}
+
+impl Sprite {
+    // Panics, at compile time for const sprites, if the data is the wrong size.
+    pub const fn new(width: usize, height: usize, data: SpriteData) -> Self {
+        let mask_len = width.div_ceil(8) * height;
+        match data {
+            SpriteData::Mask(mask) => assert!(mask.len() == mask_len),
+            SpriteData::Indexed { pixels, .. } => assert!(pixels.len() == width * height),
+            SpriteData::Rgb565 { pixels, mask } => {
+                assert!(pixels.len() == width * height);
+                if let Some(mask) = mask {
+                    assert!(mask.len() == mask_len);
+                }
+            }
+        }
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn data(&self) -> SpriteData {
+        self.data
+    }
+
+    // None where the sprite is transparent. `color` is only used by masks.
+    pub fn pixel(&self, x: usize, y: usize, color: Color) -> Option<Color> {
+        let index = y * self.width + x;
+        match self.data {
+            SpriteData::Mask(mask) => self.masked(mask, x, y).then_some(color),
+            SpriteData::Indexed {
+                palette,
+                pixels,
+                transparent,
+            } => match pixels[index] {
+                pixel if Some(pixel) == transparent => None,
+                pixel => Some(palette[pixel as usize]),
+            },
+            SpriteData::Rgb565 { pixels, mask } => match mask {
+                Some(mask) if !self.masked(mask, x, y) => None,
+                _ => Some(Color::from_rgb565(pixels[index])),
+            },
+        }
+    }
+
+    // Draws the sprite with its top left corner at `position`, leaving
+    // transparent pixels alone. Masks are drawn in `color`.
+    pub fn draw<D: DrawTarget>(
+        &self,
+        target: &mut D,
+        position: Point,
+        color: Color,
+    ) -> Result<(), D::Error>
+    where
+        D::Color: From<Color>,
+    {
+        let pixels = (0..self.height).flat_map(|y| (0..self.width).map(move |x| (x, y)));
+        target.draw_iter(pixels.filter_map(|(x, y)| {
+            let color = self.pixel(x, y, color)?;
+            let point = position + Point::new(x as i32, y as i32);
+            Some(Pixel(point, color.into()))
+        }))
+    }
+
+    fn masked(&self, mask: &[u8], x: usize, y: usize) -> bool {
+        let row = &mask[y * self.width.div_ceil(8)..];
+        (row[x / 8] << (x % 8)) & 0x80 > 0
+    }
+}
@@ -0,0 +1,311 @@
+use super::Font;
+
+// Glyphs for printable ASCII, from ' ' up to '~'. Each glyph is stored a column
+// at a time, left to right, with the top row in the lowest bit.
+
+// Tiny, but still legible. Fits 16 characters across a 64 pixel wide panel.
+pub const FONT_3X5: Font = Font::new(3, 5, &GLYPHS_3X5);
+
+// The classic HD44780 style font.
+pub const FONT_5X7: Font = Font::new(5, 7, &GLYPHS_5X7);
+
+// The public domain 6x12 font from X11's misc-fixed set. Fits 9 characters
+// across and 5 lines down a 64 pixel square, with room for descenders.
+pub const FONT_6X12: Font = Font::new(6, 12, &GLYPHS_6X12);
+
+#[rustfmt::skip]
+const GLYPHS_3X5: [u16; 95 * 3] = [
+    0x00, 0x00, 0x00, // space
+    0x00, 0x17, 0x00, // !
+    0x03, 0x00, 0x03, // "
+    0x1f, 0x0a, 0x1f, // #
+    0x12, 0x1f, 0x09, // $
+    0x19, 0x04, 0x13, // %
+    0x0a, 0x15, 0x1a, // &
+    0x00, 0x03, 0x00, // '
+    0x00, 0x0e, 0x11, // (
+    0x11, 0x0e, 0x00, // )
+    0x0a, 0x04, 0x0a, // *
+    0x04, 0x0e, 0x04, // +
+    0x10, 0x08, 0x00, // ,
+    0x04, 0x04, 0x04, // -
+    0x00, 0x10, 0x00, // .
+    0x18, 0x04, 0x03, // /
+    0x1f, 0x11, 0x1f, // 0
+    0x12, 0x1f, 0x10, // 1
+    0x1d, 0x15, 0x17, // 2
+    0x11, 0x15, 0x1f, // 3
+    0x07, 0x04, 0x1f, // 4
+    0x17, 0x15, 0x1d, // 5
+    0x1f, 0x15, 0x1d, // 6
+    0x01, 0x1d, 0x03, // 7
+    0x1f, 0x15, 0x1f, // 8
+    0x17, 0x15, 0x1f, // 9
+    0x00, 0x0a, 0x00, // :
+    0x10, 0x0a, 0x00, // ;
+    0x04, 0x0a, 0x11, // <
+    0x0a, 0x0a, 0x0a, // =
+    0x11, 0x0a, 0x04, // >
+    0x01, 0x15, 0x03, // ?
+    0x0e, 0x15, 0x16, // @
+    0x1e, 0x05, 0x1e, // A
+    0x1f, 0x15, 0x0a, // B
+    0x0e, 0x11, 0x11, // C
+    0x1f, 0x11, 0x0e, // D
+    0x1f, 0x15, 0x11, // E
+    0x1f, 0x05, 0x01, // F
+    0x0e, 0x11, 0x1d, // G
+    0x1f, 0x04, 0x1f, // H
+    0x11, 0x1f, 0x11, // I
+    0x08, 0x10, 0x0f, // J
+    0x1f, 0x04, 0x1b, // K
+    0x1f, 0x10, 0x10, // L
+    0x1f, 0x06, 0x1f, // M
+    0x1f, 0x0e, 0x1f, // N
+    0x0e, 0x11, 0x0e, // O
+    0x1f, 0x05, 0x02, // P
+    0x0e, 0x19, 0x1e, // Q
+    0x1f, 0x05, 0x1a, // R
+    0x12, 0x15, 0x09, // S
+    0x01, 0x1f, 0x01, // T
+    0x0f, 0x10, 0x1f, // U
+    0x07, 0x18, 0x07, // V
+    0x1f, 0x0c, 0x1f, // W
+    0x1b, 0x04, 0x1b, // X
+    0x03, 0x1c, 0x03, // Y
+    0x19, 0x15, 0x13, // Z
+    0x1f, 0x11, 0x00, // [
+    0x03, 0x04, 0x18, // \
+    0x00, 0x11, 0x1f, // ]
+    0x02, 0x01, 0x02, // ^
+    0x10, 0x10, 0x10, // _
+    0x01, 0x02, 0x00, // `
+    0x0c, 0x12, 0x1e, // a
+    0x1f, 0x12, 0x0c, // b
+    0x0c, 0x12, 0x12, // c
+    0x0c, 0x12, 0x1f, // d
+    0x0c, 0x1a, 0x16, // e
+    0x04, 0x1e, 0x05, // f
+    0x06, 0x15, 0x0f, // g
+    0x1f, 0x02, 0x1c, // h
+    0x00, 0x1d, 0x00, // i
+    0x08, 0x10, 0x0d, // j
+    0x1f, 0x0c, 0x12, // k
+    0x11, 0x1f, 0x10, // l
+    0x1e, 0x0e, 0x1e, // m
+    0x1e, 0x02, 0x1c, // n
+    0x0c, 0x12, 0x0c, // o
+    0x1e, 0x0a, 0x04, // p
+    0x04, 0x0a, 0x1e, // q
+    0x1c, 0x02, 0x02, // r
+    0x14, 0x1e, 0x0a, // s
+    0x02, 0x1f, 0x12, // t
+    0x0e, 0x10, 0x1e, // u
+    0x06, 0x18, 0x06, // v
+    0x1e, 0x1c, 0x1e, // w
+    0x12, 0x0c, 0x12, // x
+    0x16, 0x18, 0x0e, // y
+    0x1a, 0x1e, 0x16, // z
+    0x04, 0x1f, 0x11, // {
+    0x00, 0x1f, 0x00, // |
+    0x11, 0x1f, 0x04, // }
+    0x04, 0x06, 0x02, // ~
+];
+
+#[rustfmt::skip]
+const GLYPHS_5X7: [u16; 95 * 5] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, // space
+    0x00, 0x00, 0x5f, 0x00, 0x00, // !
+    0x00, 0x07, 0x00, 0x07, 0x00, // "
+    0x14, 0x7f, 0x14, 0x7f, 0x14, // #
+    0x24, 0x2a, 0x7f, 0x2a, 0x12, // $
+    0x23, 0x13, 0x08, 0x64, 0x62, // %
+    0x36, 0x49, 0x55, 0x22, 0x50, // &
+    0x00, 0x05, 0x03, 0x00, 0x00, // '
+    0x00, 0x1c, 0x22, 0x41, 0x00, // (
+    0x00, 0x41, 0x22, 0x1c, 0x00, // )
+    0x14, 0x08, 0x3e, 0x08, 0x14, // *
+    0x08, 0x08, 0x3e, 0x08, 0x08, // +
+    0x00, 0x50, 0x30, 0x00, 0x00, // ,
+    0x08, 0x08, 0x08, 0x08, 0x08, // -
+    0x00, 0x60, 0x60, 0x00, 0x00, // .
+    0x20, 0x10, 0x08, 0x04, 0x02, // /
+    0x3e, 0x51, 0x49, 0x45, 0x3e, // 0
+    0x00, 0x42, 0x7f, 0x40, 0x00, // 1
+    0x42, 0x61, 0x51, 0x49, 0x46, // 2
+    0x21, 0x41, 0x45, 0x4b, 0x31, // 3
+    0x18, 0x14, 0x12, 0x7f, 0x10, // 4
+    0x27, 0x45, 0x45, 0x45, 0x39, // 5
+    0x3c, 0x4a, 0x49, 0x49, 0x30, // 6
+    0x01, 0x71, 0x09, 0x05, 0x03, // 7
+    0x36, 0x49, 0x49, 0x49, 0x36, // 8
+    0x06, 0x49, 0x49, 0x29, 0x1e, // 9
+    0x00, 0x36, 0x36, 0x00, 0x00, // :
+    0x00, 0x56, 0x36, 0x00, 0x00, // ;
+    0x08, 0x14, 0x22, 0x41, 0x00, // <
+    0x14, 0x14, 0x14, 0x14, 0x14, // =
+    0x00, 0x41, 0x22, 0x14, 0x08, // >
+    0x02, 0x01, 0x51, 0x09, 0x06, // ?
+    0x32, 0x49, 0x79, 0x41, 0x3e, // @
+    0x7e, 0x11, 0x11, 0x11, 0x7e, // A
+    0x7f, 0x49, 0x49, 0x49, 0x36, // B
+    0x3e, 0x41, 0x41, 0x41, 0x22, // C
+    0x7f, 0x41, 0x41, 0x22, 0x1c, // D
+    0x7f, 0x49, 0x49, 0x49, 0x41, // E
+    0x7f, 0x09, 0x09, 0x01, 0x01, // F
+    0x3e, 0x41, 0x41, 0x51, 0x32, // G
+    0x7f, 0x08, 0x08, 0x08, 0x7f, // H
+    0x00, 0x41, 0x7f, 0x41, 0x00, // I
+    0x20, 0x40, 0x41, 0x3f, 0x01, // J
+    0x7f, 0x08, 0x14, 0x22, 0x41, // K
+    0x7f, 0x40, 0x40, 0x40, 0x40, // L
+    0x7f, 0x02, 0x04, 0x02, 0x7f, // M
+    0x7f, 0x04, 0x08, 0x10, 0x7f, // N
+    0x3e, 0x41, 0x41, 0x41, 0x3e, // O
+    0x7f, 0x09, 0x09, 0x09, 0x06, // P
+    0x3e, 0x41, 0x51, 0x21, 0x5e, // Q
+    0x7f, 0x09, 0x19, 0x29, 0x46, // R
+    0x46, 0x49, 0x49, 0x49, 0x31, // S
+    0x01, 0x01, 0x7f, 0x01, 0x01, // T
+    0x3f, 0x40, 0x40, 0x40, 0x3f, // U
+    0x1f, 0x20, 0x40, 0x20, 0x1f, // V
+    0x7f, 0x20, 0x18, 0x20, 0x7f, // W
+    0x63, 0x14, 0x08, 0x14, 0x63, // X
+    0x03, 0x04, 0x78, 0x04, 0x03, // Y
+    0x61, 0x51, 0x49, 0x45, 0x43, // Z
+    0x00, 0x7f, 0x41, 0x41, 0x00, // [
+    0x02, 0x04, 0x08, 0x10, 0x20, // \
+    0x00, 0x41, 0x41, 0x7f, 0x00, // ]
+    0x04, 0x02, 0x01, 0x02, 0x04, // ^
+    0x40, 0x40, 0x40, 0x40, 0x40, // _
+    0x00, 0x01, 0x02, 0x04, 0x00, // `
+    0x20, 0x54, 0x54, 0x54, 0x78, // a
+    0x7f, 0x48, 0x44, 0x44, 0x38, // b
+    0x38, 0x44, 0x44, 0x44, 0x20, // c
+    0x38, 0x44, 0x44, 0x48, 0x7f, // d
+    0x38, 0x54, 0x54, 0x54, 0x18, // e
+    0x08, 0x7e, 0x09, 0x01, 0x02, // f
+    0x08, 0x54, 0x54, 0x54, 0x3c, // g
+    0x7f, 0x08, 0x04, 0x04, 0x78, // h
+    0x00, 0x44, 0x7d, 0x40, 0x00, // i
+    0x20, 0x40, 0x44, 0x3d, 0x00, // j
+    0x00, 0x7f, 0x10, 0x28, 0x44, // k
+    0x00, 0x41, 0x7f, 0x40, 0x00, // l
+    0x7c, 0x04, 0x18, 0x04, 0x78, // m
+    0x7c, 0x08, 0x04, 0x04, 0x78, // n
+    0x38, 0x44, 0x44, 0x44, 0x38, // o
+    0x7c, 0x14, 0x14, 0x14, 0x08, // p
+    0x08, 0x14, 0x14, 0x18, 0x7c, // q
+    0x7c, 0x08, 0x04, 0x04, 0x08, // r
+    0x48, 0x54, 0x54, 0x54, 0x20, // s
+    0x04, 0x3f, 0x44, 0x40, 0x20, // t
+    0x3c, 0x40, 0x40, 0x20, 0x7c, // u
+    0x1c, 0x20, 0x40, 0x20, 0x1c, // v
+    0x3c, 0x40, 0x30, 0x40, 0x3c, // w
+    0x44, 0x28, 0x10, 0x28, 0x44, // x
+    0x0c, 0x50, 0x50, 0x50, 0x3c, // y
+    0x44, 0x64, 0x54, 0x4c, 0x44, // z
+    0x00, 0x08, 0x36, 0x41, 0x00, // {
+    0x00, 0x00, 0x7f, 0x00, 0x00, // |
+    0x00, 0x41, 0x36, 0x08, 0x00, // }
+    0x08, 0x04, 0x08, 0x10, 0x08, // ~
+];
+
+#[rustfmt::skip]
+const GLYPHS_6X12: [u16; 95 * 6] = [
+    0x000, 0x000, 0x000, 0x000, 0x000, 0x000, // space
+    0x000, 0x000, 0x2f8, 0x000, 0x000, 0x000, // !
+    0x000, 0x01c, 0x000, 0x01c, 0x000, 0x000, // "
+    0x120, 0x3f0, 0x120, 0x3f0, 0x120, 0x000, // #
+    0x130, 0x248, 0x7fc, 0x248, 0x190, 0x000, // $
+    0x318, 0x098, 0x040, 0x320, 0x318, 0x000, // %
+    0x1b0, 0x248, 0x2b0, 0x100, 0x280, 0x000, // &
+    0x000, 0x000, 0x01c, 0x000, 0x000, 0x000, // '
+    0x000, 0x0e0, 0x318, 0x404, 0x000, 0x000, // (
+    0x000, 0x404, 0x318, 0x0e0, 0x000, 0x000, // )
+    0x110, 0x0a0, 0x3f8, 0x0a0, 0x110, 0x000, // *
+    0x040, 0x040, 0x1f0, 0x040, 0x040, 0x000, // +
+    0x400, 0x700, 0x300, 0x000, 0x000, 0x000, // ,
+    0x040, 0x040, 0x040, 0x040, 0x040, 0x000, // -
+    0x000, 0x300, 0x300, 0x000, 0x000, 0x000, // .
+    0x200, 0x180, 0x040, 0x030, 0x008, 0x000, // /
+    0x000, 0x1f0, 0x208, 0x208, 0x1f0, 0x000, // 0
+    0x000, 0x210, 0x3f8, 0x200, 0x000, 0x000, // 1
+    0x210, 0x308, 0x288, 0x248, 0x230, 0x000, // 2
+    0x108, 0x208, 0x248, 0x268, 0x198, 0x000, // 3
+    0x0c0, 0x0a0, 0x090, 0x3f8, 0x080, 0x000, // 4
+    0x138, 0x228, 0x228, 0x228, 0x1c8, 0x000, // 5
+    0x1e0, 0x250, 0x248, 0x248, 0x180, 0x000, // 6
+    0x008, 0x008, 0x388, 0x068, 0x018, 0x000, // 7
+    0x1b0, 0x248, 0x248, 0x248, 0x1b0, 0x000, // 8
+    0x030, 0x248, 0x248, 0x148, 0x0f0, 0x000, // 9
+    0x000, 0x360, 0x360, 0x000, 0x000, 0x000, // :
+    0x400, 0x760, 0x360, 0x000, 0x000, 0x000, // ;
+    0x000, 0x040, 0x0a0, 0x110, 0x000, 0x000, // <
+    0x0a0, 0x0a0, 0x0a0, 0x0a0, 0x0a0, 0x000, // =
+    0x000, 0x110, 0x0a0, 0x040, 0x000, 0x000, // >
+    0x010, 0x008, 0x2c8, 0x028, 0x010, 0x000, // ?
+    0x1f0, 0x208, 0x2e8, 0x2a8, 0x0f0, 0x000, // @
+    0x3f0, 0x048, 0x048, 0x048, 0x3f0, 0x000, // A
+    0x208, 0x3f8, 0x248, 0x248, 0x1b0, 0x000, // B
+    0x1f0, 0x208, 0x208, 0x208, 0x110, 0x000, // C
+    0x208, 0x3f8, 0x208, 0x208, 0x1f0, 0x000, // D
+    0x3f8, 0x248, 0x248, 0x248, 0x208, 0x000, // E
+    0x3f8, 0x048, 0x048, 0x048, 0x008, 0x000, // F
+    0x1f0, 0x208, 0x208, 0x288, 0x190, 0x000, // G
+    0x3f8, 0x040, 0x040, 0x040, 0x3f8, 0x000, // H
+    0x000, 0x208, 0x3f8, 0x208, 0x000, 0x000, // I
+    0x100, 0x200, 0x208, 0x1f8, 0x008, 0x000, // J
+    0x3f8, 0x040, 0x0a0, 0x110, 0x208, 0x000, // K
+    0x3f8, 0x200, 0x200, 0x200, 0x200, 0x000, // L
+    0x3f8, 0x010, 0x020, 0x010, 0x3f8, 0x000, // M
+    0x3f8, 0x020, 0x040, 0x080, 0x3f8, 0x000, // N
+    0x1f0, 0x208, 0x208, 0x208, 0x1f0, 0x000, // O
+    0x3f8, 0x048, 0x048, 0x048, 0x030, 0x000, // P
+    0x1f0, 0x208, 0x288, 0x108, 0x2f0, 0x000, // Q
+    0x3f8, 0x048, 0x0c8, 0x148, 0x230, 0x000, // R
+    0x130, 0x248, 0x248, 0x248, 0x190, 0x000, // S
+    0x008, 0x008, 0x3f8, 0x008, 0x008, 0x000, // T
+    0x1f8, 0x200, 0x200, 0x200, 0x1f8, 0x000, // U
+    0x078, 0x180, 0x200, 0x180, 0x078, 0x000, // V
+    0x1f8, 0x200, 0x180, 0x200, 0x1f8, 0x000, // W
+    0x318, 0x0a0, 0x040, 0x0a0, 0x318, 0x000, // X
+    0x018, 0x020, 0x3c0, 0x020, 0x018, 0x000, // Y
+    0x308, 0x288, 0x248, 0x228, 0x218, 0x000, // Z
+    0x000, 0x7fc, 0x404, 0x404, 0x000, 0x000, // [
+    0x008, 0x030, 0x040, 0x180, 0x200, 0x000, // \
+    0x000, 0x404, 0x404, 0x7fc, 0x000, 0x000, // ]
+    0x010, 0x008, 0x004, 0x008, 0x010, 0x000, // ^
+    0x800, 0x800, 0x800, 0x800, 0x800, 0x000, // _
+    0x000, 0x004, 0x008, 0x010, 0x000, 0x000, // `
+    0x100, 0x2a0, 0x2a0, 0x2a0, 0x3c0, 0x000, // a
+    0x3f8, 0x220, 0x220, 0x220, 0x1c0, 0x000, // b
+    0x1c0, 0x220, 0x220, 0x220, 0x140, 0x000, // c
+    0x1c0, 0x220, 0x220, 0x220, 0x3f8, 0x000, // d
+    0x1c0, 0x2a0, 0x2a0, 0x2a0, 0x040, 0x000, // e
+    0x040, 0x3f0, 0x048, 0x008, 0x010, 0x000, // f
+    0x1c0, 0xa20, 0xa20, 0xa20, 0x7c0, 0x000, // g
+    0x3f8, 0x020, 0x020, 0x020, 0x3c0, 0x000, // h
+    0x000, 0x220, 0x3e8, 0x200, 0x000, 0x000, // i
+    0x000, 0x400, 0x800, 0x820, 0x7e8, 0x000, // j
+    0x3f8, 0x080, 0x080, 0x140, 0x220, 0x000, // k
+    0x000, 0x208, 0x3f8, 0x200, 0x000, 0x000, // l
+    0x3e0, 0x020, 0x3c0, 0x020, 0x3c0, 0x000, // m
+    0x3e0, 0x040, 0x020, 0x020, 0x3c0, 0x000, // n
+    0x1c0, 0x220, 0x220, 0x220, 0x1c0, 0x000, // o
+    0xfe0, 0x220, 0x220, 0x220, 0x1c0, 0x000, // p
+    0x1c0, 0x220, 0x220, 0x220, 0xfe0, 0x000, // q
+    0x3e0, 0x040, 0x020, 0x020, 0x040, 0x000, // r
+    0x240, 0x2a0, 0x2a0, 0x2a0, 0x120, 0x000, // s
+    0x020, 0x020, 0x1f8, 0x220, 0x220, 0x000, // t
+    0x1e0, 0x200, 0x200, 0x100, 0x3e0, 0x000, // u
+    0x0e0, 0x100, 0x200, 0x100, 0x0e0, 0x000, // v
+    0x1e0, 0x200, 0x180, 0x200, 0x1e0, 0x000, // w
+    0x220, 0x140, 0x080, 0x140, 0x220, 0x000, // x
+    0x8e0, 0x500, 0x200, 0x100, 0x0e0, 0x000, // y
+    0x220, 0x320, 0x2a0, 0x260, 0x220, 0x000, // z
+    0x000, 0x040, 0x3b8, 0x404, 0x000, 0x000, // {
+    0x000, 0x000, 0x7fc, 0x000, 0x000, 0x000, // |
+    0x000, 0x404, 0x3b8, 0x040, 0x000, 0x000, // }
+    0x0c0, 0x020, 0x040, 0x080, 0x060, 0x000, // ~
+];
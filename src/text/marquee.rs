@@ -0,0 +1,216 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget, geometry::Point, pixelcolor::PixelColor, primitives::Rectangle,
+};
+
+use super::{Clipped, TextStyle};
+
+const MICROS_PER_SECOND: u64 = 1_000_000;
+
+// What a marquee does once its text has scrolled all the way in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum MarqueeEnd {
+    // Keeps going, with the next copy of the text following `gap` pixels
+    // behind the last.
+    Loop { gap: u32 },
+    // Stops with the end of the text at the right edge of the area, or with
+    // the whole text at the left edge if it's short enough to fit.
+    Stop,
+}
+
+// A line of text scrolling right to left across an area, a pixel at a time.
+// The text is copied in, up to N bytes of it, so the marquee can hold on to it
+// without borrowing.
+pub struct Marquee<C, const N: usize> {
+    style: TextStyle<C>,
+    area: Rectangle,
+    end: MarqueeEnd,
+    // Pixels per second.
+    speed: u32,
+    text: Text<N>,
+    // Text waiting to follow the current text in, and how many copies of the
+    // current text go before it.
+    next: Option<Text<N>>,
+    next_copies: u32,
+    // Text set while `next` was already showing, which follows it in turn.
+    queued: Option<Text<N>>,
+    // How far the current text has moved from just past the right edge of the
+    // area, in millionths of a pixel so slow speeds don't lose time to
+    // rounding.
+    scrolled: u64,
+}
+
+impl<C: PixelColor, const N: usize> Marquee<C, N> {
+    pub fn new(
+        style: TextStyle<C>,
+        area: Rectangle,
+        end: MarqueeEnd,
+        speed: u32,
+        text: &str,
+    ) -> Self {
+        Self {
+            style,
+            area,
+            end,
+            speed,
+            text: Text::new(text),
+            next: None,
+            next_copies: 0,
+            queued: None,
+            scrolled: 0,
+        }
+    }
+
+    pub fn set_speed(&mut self, speed: u32) {
+        self.speed = speed;
+    }
+
+    pub fn set_style(&mut self, style: TextStyle<C>) {
+        self.style = style;
+    }
+
+    // Queues up new text to scroll in behind the current text, `gap` pixels
+    // after the first copy of it that hasn't appeared yet when looping, so
+    // nothing on screen jumps. Replaces any text that's queued but hasn't
+    // appeared yet.
+    pub fn set_text(&mut self, text: &str) {
+        let text = Text::new(text);
+        if self.next.is_some() && self.next_showing() {
+            self.queued = Some(text);
+        } else {
+            self.next_copies = self.copies_before_next();
+            self.next = Some(text);
+        }
+    }
+
+    // Starts over with new text just past the right edge.
+    pub fn restart(&mut self, text: &str) {
+        self.text = Text::new(text);
+        self.next = None;
+        self.queued = None;
+        self.scrolled = 0;
+    }
+
+    pub fn text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    // Whether the marquee has stopped for good. Never true when looping.
+    pub fn finished(&self) -> bool {
+        self.next.is_none() && Some(self.scrolled) == self.stop()
+    }
+
+    // Moves the text along by however far it goes in `micros` microseconds.
+    pub fn advance(&mut self, micros: u32) {
+        self.scrolled += self.speed as u64 * micros as u64;
+
+        // Once the current text has gone past the left edge, whatever follows
+        // it takes its place.
+        let period = self.step() * MICROS_PER_SECOND;
+        let gone = self.area.size.width as u64 * MICROS_PER_SECOND + period;
+        if self.scrolled >= gone && (self.next.is_some() || self.stop().is_none()) {
+            self.scrolled -= period;
+            self.next_copies = self.next_copies.saturating_sub(1);
+            if self.next_copies == 0 {
+                if let Some(next) = self.next.take() {
+                    self.text = next;
+                    if let Some(queued) = self.queued.take() {
+                        self.next_copies = self.copies_before_next();
+                        self.next = Some(queued);
+                    }
+                }
+            }
+        }
+
+        if let (None, Some(stop)) = (&self.next, self.stop()) {
+            self.scrolled = self.scrolled.min(stop);
+        }
+    }
+
+    pub fn draw<D: DrawTarget<Color = C>>(&self, target: &mut D) -> Result<(), D::Error> {
+        let target = &mut Clipped {
+            target,
+            area: self.area,
+        };
+        let right = self.area.top_left.x + self.area.size.width as i32;
+        let mut x = right - (self.scrolled / MICROS_PER_SECOND) as i32;
+        let mut text = &self.text;
+        let mut copies = 0;
+        while x < right {
+            self.style
+                .draw(text.as_str(), Point::new(x, self.area.top_left.y), target)?;
+            let step = self.style.line_width(text.as_str()) + self.gap();
+            if step == 0 {
+                break;
+            }
+            x += step as i32;
+            copies += 1;
+            text = match (&self.next, self.end) {
+                (Some(next), _) if copies >= self.next_copies => next,
+                (Some(_), _) | (None, MarqueeEnd::Loop { .. }) => &self.text,
+                (None, MarqueeEnd::Stop) => break,
+            };
+        }
+        Ok(())
+    }
+
+    // How far apart copies of the current text start, in pixels.
+    fn step(&self) -> u64 {
+        (self.style.line_width(self.text.as_str()) + self.gap()) as u64
+    }
+
+    // Queued text goes after the first copy of the current text that's still
+    // past the right edge, rather than replacing copies already on screen.
+    fn copies_before_next(&self) -> u32 {
+        match self.step() {
+            0 => 1,
+            step => (self.scrolled / MICROS_PER_SECOND).div_ceil(step).max(1) as u32,
+        }
+    }
+
+    fn next_showing(&self) -> bool {
+        (self.next_copies as u64 * self.step()) < self.scrolled / MICROS_PER_SECOND
+    }
+
+    fn gap(&self) -> u32 {
+        match self.end {
+            MarqueeEnd::Loop { gap } => gap,
+            // Queued text still needs some room from what's in front of it.
+            MarqueeEnd::Stop => self.area.size.width,
+        }
+    }
+
+    // How far the current text scrolls before stopping, if it ever does.
+    fn stop(&self) -> Option<u64> {
+        match self.end {
+            MarqueeEnd::Loop { .. } => None,
+            MarqueeEnd::Stop => {
+                let width = self.area.size.width;
+                let text_width = self.style.line_width(self.text.as_str());
+                Some(width.max(text_width) as u64 * MICROS_PER_SECOND)
+            }
+        }
+    }
+}
+
+// Text copied into a fixed size buffer, cut short at a character boundary if
+// it doesn't fit.
+struct Text<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Text<N> {
+    fn new(text: &str) -> Self {
+        let mut len = text.len().min(N);
+        while !text.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut bytes = [0; N];
+        bytes[..len].copy_from_slice(&text.as_bytes()[..len]);
+        Self { bytes, len }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
@@ -0,0 +1,311 @@
+// Bitmap fonts and text layout. Text is drawn through embedded-graphics, so it
+// goes into a Canvas, an Rgb565Frame or any other draw target, and is clipped
+// by it like everything else.
+
+mod fonts;
+mod marquee;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    pixelcolor::PixelColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+pub use fonts::{FONT_3X5, FONT_5X7, FONT_6X12};
+pub use marquee::{Marquee, MarqueeEnd};
+
+const GLYPH_COUNT: usize = ('~' as usize) - (' ' as usize) + 1;
+
+// A monospaced font covering printable ASCII. Any other character is drawn as
+// a question mark.
+#[derive(Copy, Clone)]
+pub struct Font {
+    width: u32,
+    height: u32,
+    glyphs: &'static [u16],
+}
+
+impl Font {
+    // Glyphs go from ' ' to '~', `width` columns each, left to right with the
+    // top row in the lowest bit. That limits fonts to 16 pixels tall.
+    pub const fn new(width: u32, height: u32, glyphs: &'static [u16]) -> Self {
+        assert!(height <= 16);
+        assert!(glyphs.len() == GLYPH_COUNT * width as usize);
+        Self {
+            width,
+            height,
+            glyphs,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn glyph(&self, c: char) -> &'static [u16] {
+        let c = if (' '..='~').contains(&c) { c } else { '?' };
+        let width = self.width as usize;
+        &self.glyphs[(c as usize - ' ' as usize) * width..][..width]
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, defmt::Format)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+// How to draw text: which font, in what color and at what size. Spacing is in
+// font pixels, so it grows with the scale.
+#[derive(Copy, Clone)]
+pub struct TextStyle<C> {
+    pub font: Font,
+    pub color: C,
+    scale: u32,
+    letter_spacing: u32,
+    line_spacing: u32,
+}
+
+impl<C: PixelColor> TextStyle<C> {
+    // One pixel between characters and between lines.
+    pub const fn new(font: Font, color: C) -> Self {
+        Self {
+            font,
+            color,
+            scale: 1,
+            letter_spacing: 1,
+            line_spacing: 1,
+        }
+    }
+
+    // Draws every font pixel as a `scale` by `scale` square.
+    pub const fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = if scale > 0 { scale } else { 1 };
+        self
+    }
+
+    pub const fn with_spacing(mut self, letter_spacing: u32, line_spacing: u32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    // How far apart characters start.
+    pub fn advance(&self) -> u32 {
+        (self.font.width + self.letter_spacing) * self.scale
+    }
+
+    // How far apart lines start.
+    pub fn line_height(&self) -> u32 {
+        (self.font.height + self.line_spacing) * self.scale
+    }
+
+    // The width of a single line, ignoring any line breaks in it.
+    pub fn line_width(&self, line: &str) -> u32 {
+        match line.chars().count() as u32 {
+            0 => 0,
+            count => count * self.advance() - self.letter_spacing * self.scale,
+        }
+    }
+
+    // The size of the box `draw` fills, with a line for every '\n'. Empty text
+    // still takes up one line.
+    pub fn measure(&self, text: &str) -> Size {
+        let (width, lines) = text
+            .split('\n')
+            .fold((0, 0), |(width, lines), line| (width.max(self.line_width(line)), lines + 1));
+        Size::new(width, self.lines_height(lines))
+    }
+
+    // Like measure, but for text wrapped to `width` the way draw_wrapped
+    // wraps it.
+    pub fn measure_wrapped(&self, text: &str, width: u32) -> Size {
+        let (width, lines) = self
+            .wrap(text, width)
+            .fold((0, 0), |(width, lines), line| (width.max(self.line_width(line)), lines + 1));
+        Size::new(width, self.lines_height(lines))
+    }
+
+    // Draws text with its top left corner at `position`, starting a new line
+    // at every '\n'. Returns where the next character would have gone.
+    pub fn draw<D: DrawTarget<Color = C>>(
+        &self,
+        text: &str,
+        position: Point,
+        target: &mut D,
+    ) -> Result<Point, D::Error> {
+        let mut cursor = position;
+        for c in text.chars() {
+            if c == '\n' {
+                cursor = Point::new(position.x, cursor.y + self.line_height() as i32);
+            } else {
+                self.draw_char(c, cursor, target)?;
+                cursor.x += self.advance() as i32;
+            }
+        }
+        Ok(cursor)
+    }
+
+    // Lays text out inside `area`, breaking lines between words so they fit
+    // its width and aligning each line within it. Words too long for a line
+    // of their own are split, lines that would run past the bottom of the
+    // area are left out, and anything else sticking out of it is clipped.
+    pub fn draw_wrapped<D: DrawTarget<Color = C>>(
+        &self,
+        text: &str,
+        area: Rectangle,
+        alignment: Alignment,
+        target: &mut D,
+    ) -> Result<(), D::Error> {
+        let target = &mut Clipped { target, area };
+        let glyph_height = self.font.height * self.scale;
+        let mut y = 0;
+        for line in self.wrap(text, area.size.width) {
+            if y + glyph_height > area.size.height {
+                break;
+            }
+
+            let free = area.size.width.saturating_sub(self.line_width(line));
+            let x = match alignment {
+                Alignment::Left => 0,
+                Alignment::Center => free / 2,
+                Alignment::Right => free,
+            };
+            self.draw(line, area.top_left + Point::new(x as i32, y as i32), target)?;
+            y += self.line_height();
+        }
+        Ok(())
+    }
+
+    // Splits text into the lines draw_wrapped would draw in an area `width`
+    // pixels wide.
+    pub fn wrap<'a>(&self, text: &'a str, width: u32) -> Lines<'a> {
+        let columns = (width + self.letter_spacing * self.scale) / self.advance();
+        Lines {
+            rest: Some(text),
+            columns: (columns as usize).max(1),
+        }
+    }
+
+    fn lines_height(&self, lines: u32) -> u32 {
+        (lines * self.line_height()).saturating_sub(self.line_spacing * self.scale)
+    }
+
+    fn draw_char<D: DrawTarget<Color = C>>(
+        &self,
+        c: char,
+        position: Point,
+        target: &mut D,
+    ) -> Result<(), D::Error> {
+        let scale = self.scale;
+        let cell = Rectangle::new(
+            position,
+            Size::new(self.font.width * scale, self.font.height * scale),
+        );
+        if cell.intersection(&target.bounding_box()).is_zero_sized() {
+            return Ok(());
+        }
+
+        for (x, column) in self.font.glyph(c).iter().enumerate() {
+            for y in 0..self.font.height {
+                if (column >> y) & 1 > 0 {
+                    let offset = Point::new((x as u32 * scale) as i32, (y * scale) as i32);
+                    target.fill_solid(
+                        &Rectangle::new(position + offset, Size::new(scale, scale)),
+                        self.color,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// The lines of word wrapped text, without the spaces they were broken at.
+pub struct Lines<'a> {
+    rest: Option<&'a str>,
+    // How many characters fit on a line.
+    columns: usize,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest?;
+        let (paragraph, after) = match rest.find('\n') {
+            Some(end) => (&rest[..end], Some(&rest[end + 1..])),
+            None => (rest, None),
+        };
+
+        // Where the first character that doesn't fit starts, if there is one.
+        let Some((end, _)) = paragraph.char_indices().nth(self.columns) else {
+            self.rest = after;
+            return Some(paragraph.trim_end_matches(' '));
+        };
+
+        // Break at the last space that fits, unless that would leave the line
+        // empty, in which case the word has to be split.
+        let split = if paragraph[end..].starts_with(' ') {
+            Some(end)
+        } else {
+            paragraph[..end].rfind(' ')
+        };
+        let (line, next) = match split {
+            Some(split) if !paragraph[..split].trim_end_matches(' ').is_empty() => {
+                (paragraph[..split].trim_end_matches(' '), &paragraph[split..])
+            }
+            _ => (&paragraph[..end], &paragraph[end..]),
+        };
+
+        let next = next.trim_start_matches(' ');
+        self.rest = if next.is_empty() {
+            after
+        } else {
+            Some(&rest[paragraph.len() - next.len()..])
+        };
+        Some(line)
+    }
+}
+
+// Restricts drawing to part of another draw target.
+pub(crate) struct Clipped<'a, D> {
+    pub target: &'a mut D,
+    pub area: Rectangle,
+}
+
+impl<D: DrawTarget> Dimensions for Clipped<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.area.intersection(&self.target.bounding_box())
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Clipped<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let area = self.area;
+        self.target
+            .draw_iter(pixels.into_iter().filter(|Pixel(point, _)| area.contains(*point)))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.area);
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+        self.target.fill_solid(&area, color)
+    }
+}
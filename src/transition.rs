@@ -0,0 +1,148 @@
+// Ways of going from one frame to another over time, for when a Scheduler
+// switches scenes. Transitions work on plain matrices, since they need to mix
+// arbitrary colors.
+
+use crate::{display::Matrix, effects::math::hash};
+
+// The way the moving edge of a transition goes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub enum Effect {
+    // Fades from one frame into the other.
+    Crossfade,
+    // Uncovers the new frame behind an edge moving across the old one.
+    Wipe(Direction),
+    // Moves the new frame in, pushing the old one out ahead of it.
+    Push(Direction),
+    // Moves the new frame in over the old one, which stays where it is.
+    Slide(Direction),
+    // Switches pixels over one by one, in random order.
+    Dissolve,
+}
+
+// How progress speeds up and slows down over the course of a transition.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, defmt::Format)]
+pub enum Easing {
+    Linear,
+    // Starts slow.
+    EaseIn,
+    // Ends slow.
+    EaseOut,
+    // Starts and ends slow.
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    // Maps linear progress out of 255 onto eased progress out of 255.
+    pub const fn apply(self, t: u8) -> u8 {
+        let t = t as u32;
+        let eased = match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t / 255,
+            Easing::EaseOut => 255 - (255 - t) * (255 - t) / 255,
+            // Smoothstep: 3t² - 2t³.
+            Easing::EaseInOut => t * t * (3 * 255 - 2 * t) / (255 * 255),
+        };
+        eased as u8
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, defmt::Format)]
+pub struct Transition {
+    pub effect: Effect,
+    pub easing: Easing,
+    // In microseconds.
+    pub duration: u32,
+}
+
+impl Transition {
+    pub const fn new(effect: Effect, duration: u32) -> Self {
+        Self {
+            effect,
+            easing: Easing::EaseInOut,
+            duration,
+        }
+    }
+
+    pub const fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    // Eased progress out of 255, `elapsed` microseconds in.
+    pub fn progress(&self, elapsed: u32) -> u8 {
+        let linear = match self.duration {
+            0 => 255,
+            duration => (elapsed.min(duration) as u64 * 255 / duration as u64) as u8,
+        };
+        self.easing.apply(linear)
+    }
+
+    // Draws the transition from one frame to another, `elapsed` microseconds
+    // in. The seed picks the order pixels dissolve in.
+    pub fn draw<const W: usize, const H: usize>(
+        &self,
+        from: &Matrix<W, H>,
+        to: &Matrix<W, H>,
+        elapsed: u32,
+        seed: u32,
+        out: &mut Matrix<W, H>,
+    ) {
+        let t = self.progress(elapsed);
+        // How far a moving edge has gone.
+        let (x_offset, y_offset) = (W * t as usize / 255, H * t as usize / 255);
+
+        for (y, row) in out.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = match self.effect {
+                    Effect::Crossfade => from[y][x].lerp(to[y][x], t),
+                    Effect::Wipe(direction) => {
+                        let uncovered = match direction {
+                            Direction::Left => x >= W - x_offset,
+                            Direction::Right => x < x_offset,
+                            Direction::Up => y >= H - y_offset,
+                            Direction::Down => y < y_offset,
+                        };
+                        if uncovered {
+                            to[y][x]
+                        } else {
+                            from[y][x]
+                        }
+                    }
+                    Effect::Push(direction) => match direction {
+                        Direction::Left if x + x_offset < W => from[y][x + x_offset],
+                        Direction::Left => to[y][x + x_offset - W],
+                        Direction::Right if x >= x_offset => from[y][x - x_offset],
+                        Direction::Right => to[y][x + W - x_offset],
+                        Direction::Up if y + y_offset < H => from[y + y_offset][x],
+                        Direction::Up => to[y + y_offset - H][x],
+                        Direction::Down if y >= y_offset => from[y - y_offset][x],
+                        Direction::Down => to[y + H - y_offset][x],
+                    },
+                    Effect::Slide(direction) => match direction {
+                        Direction::Left if x >= W - x_offset => to[y][x + x_offset - W],
+                        Direction::Right if x < x_offset => to[y][x + W - x_offset],
+                        Direction::Up if y >= H - y_offset => to[y + y_offset - H][x],
+                        Direction::Down if y < y_offset => to[y + H - y_offset][x],
+                        _ => from[y][x],
+                    },
+                    Effect::Dissolve => {
+                        if t == 255 || ((hash(x as i32, y as i32, seed) >> 24) as u8) < t {
+                            to[y][x]
+                        } else {
+                            from[y][x]
+                        }
+                    }
+                };
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// Checks that color correction curves land on the right channels.
+
+use board::display::{Color, ColorCorrection};
+
+// A mid gray comes out with a different level in each channel, each the same
+// as a single curve would have given it.
+#[test]
+fn gamma_rgb_corrects_each_channel_separately() {
+    let correction = ColorCorrection::gamma_rgb(1.8, 2.2, 2.8);
+    let gray = Color::from_rgb(128, 128, 128);
+    let color = correction.apply(gray);
+
+    assert!(color.r() > color.g() && color.g() > color.b(), "{color:?}");
+    for (gamma, level) in [(1.8, color.r()), (2.2, color.g()), (2.8, color.b())] {
+        assert_eq!(
+            ColorCorrection::gamma(gamma).apply(gray).r(),
+            level,
+            "gamma {gamma}"
+        );
+    }
+
+    // The ends stay put whatever the curve.
+    assert_eq!(correction.apply(Color::black()), Color::black());
+    assert_eq!(correction.apply(Color::white()), Color::white());
+}
+
+#[test]
+fn curves_apply_in_red_green_blue_order() {
+    let mut curves = [[0; 256]; 3];
+    for (channel, curve) in curves.iter_mut().enumerate() {
+        for (i, value) in curve.iter_mut().enumerate() {
+            *value = (i / (channel + 1)) as u8;
+        }
+    }
+    let correction = ColorCorrection::from_curves(curves);
+    assert_eq!(
+        correction.apply(Color::from_rgb(90, 90, 90)),
+        Color::from_rgb(90, 45, 30)
+    );
+}
@@ -0,0 +1,109 @@
+// Checks the shared pieces effects are built from.
+
+use board::effects::{
+    math::{cos8, hash, noise, sin8, wave8, Rng},
+    Drift,
+};
+
+// Effects are redrawn at 30 fps.
+const FRAME_US: u32 = 33_333;
+
+// At 256 units a second a frame is worth 8.53 units, which used to be cut
+// down to 8 every frame.
+#[test]
+fn drift_carries_over_fractions() {
+    let mut drift = Drift::new();
+    let mut total = 0;
+    for frame in 1..=300u64 {
+        let (steps, moved) = drift.advance(FRAME_US, 256);
+        assert_eq!(steps, 1);
+        assert!(moved == 8 || moved == 9, "moved {moved}");
+        total += moved;
+        assert_eq!(total, frame * FRAME_US as u64 * 256 / 1_000_000);
+    }
+}
+
+#[test]
+fn drift_waits_for_whole_frames() {
+    let mut drift = Drift::new();
+    assert_eq!(drift.advance(FRAME_US / 2, 1_000_000), (0, 0));
+    assert_eq!(drift.advance(FRAME_US / 2, 1_000_000), (0, 0));
+    assert_eq!(drift.advance(1, 1_000_000), (1, FRAME_US as u64));
+    assert_eq!(
+        drift.advance(3 * FRAME_US, 1_000_000),
+        (3, 3 * FRAME_US as u64)
+    );
+
+    // Too slow to move in a frame, but not in a second.
+    let mut drift = Drift::new();
+    let moved: u64 = (0..30).map(|_| drift.advance(FRAME_US, 2).1).sum();
+    assert_eq!(moved, 1);
+}
+
+#[test]
+fn sine_hits_its_peaks_and_is_symmetric() {
+    assert_eq!([sin8(0), sin8(64), sin8(128), sin8(192)], [0, 127, 0, -127]);
+    assert_eq!([cos8(0), cos8(64), cos8(128), cos8(192)], [127, 0, -127, 0]);
+    for angle in 0..=255u8 {
+        assert_eq!(sin8(angle.wrapping_add(128)), -sin8(angle), "angle {angle}");
+        assert_eq!(
+            sin8(128u8.wrapping_sub(angle)),
+            sin8(angle),
+            "angle {angle}"
+        );
+        assert_eq!(wave8(angle) as i16, sin8(angle) as i16 + 127);
+    }
+    for angle in 0..64u8 {
+        assert!(sin8(angle) <= sin8(angle + 1), "angle {angle}");
+    }
+}
+
+// Close to the real thing: Bhaskara's approximation is well under a step off.
+#[test]
+fn sine_is_accurate() {
+    for angle in 0..=255u8 {
+        let exact = (angle as f64 / 256.0 * std::f64::consts::TAU).sin() * 127.0;
+        assert!((sin8(angle) as f64 - exact).abs() < 1.0, "angle {angle}");
+    }
+}
+
+// Noise goes through the hash at lattice points, and moves smoothly between
+// them.
+#[test]
+fn noise_is_smooth_between_lattice_points() {
+    let seed = 7;
+    for cell in -3..3 {
+        let x = cell * 256;
+        assert_eq!(noise(x, 512, seed), (hash(cell, 2, seed) >> 24) as u8);
+        for step in 0..256 {
+            let (a, b) = (noise(x + step, 512, seed), noise(x + step + 1, 512, seed));
+            assert!(a.abs_diff(b) <= 2, "{a} to {b} at x = {}", x + step);
+        }
+    }
+    assert_ne!(
+        (0..8).map(|x| noise(x * 256, 0, 1)).collect::<Vec<_>>(),
+        (0..8).map(|x| noise(x * 256, 0, 2)).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn rng_repeats_for_a_seed_and_stays_in_range() {
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+    for _ in 0..100 {
+        assert_eq!(a.u32(), b.u32());
+    }
+
+    // A zero seed would get stuck at zero.
+    let mut zero = Rng::new(0);
+    assert_ne!(zero.u32(), 0);
+
+    let mut rng = Rng::new(1);
+    let mut seen = [false; 10];
+    for _ in 0..1000 {
+        let value = rng.below(10);
+        assert!(value < 10);
+        seen[value as usize] = true;
+    }
+    assert!(seen.iter().all(|&seen| seen));
+}
@@ -0,0 +1,247 @@
+// Checks the decoders against images made by other encoders.
+
+use std::borrow::Cow;
+
+use board::{
+    display::{Canvas, Color, Matrix},
+    image::{Bmp, Gif, GifPlayer, ImageError, Qoi, Viewport},
+};
+use embedded_graphics_core::geometry::{Point, Size};
+
+// Odd sizes, so rows need padding and interlaced passes come out uneven.
+const W: usize = 37;
+const H: usize = 29;
+
+// Bands of flat color, gentle and steeper gradients, a few repeating colors
+// and noise with see-through pixels, so every kind of QOI chunk turns up.
+fn test_pixel(x: usize, y: usize) -> [u8; 4] {
+    let noise = ((x * 7919 + y * 104_729) ^ (x * y)) as u8;
+    match y % 7 {
+        0 | 1 => [200, 40, 90, 255],
+        2 => [(x + y) as u8, (x / 2) as u8, (100 - x) as u8, 255],
+        3 => [(x * 10) as u8, (x * 9 + y) as u8, (x * 11) as u8, 255],
+        4 => [[255, 0, 0, 255], [0, 255, 0, 255], [9, 9, 9, 255]][x % 3],
+        _ => {
+            let alpha = if (x + y).is_multiple_of(11) { 40 } else { 255 };
+            [noise, (x * 6) as u8, noise.rotate_left(3), alpha]
+        }
+    }
+}
+
+fn viewport() -> Viewport {
+    Viewport::at(Size::new(W as u32, H as u32), Point::zero())
+}
+
+// Transparent pixels aren't drawn, so they stay black.
+fn expected(pixel: impl Fn(usize, usize) -> [u8; 4]) -> Matrix<W, H> {
+    let mut matrix = [[Color::black(); W]; H];
+    for (y, row) in matrix.iter_mut().enumerate() {
+        for (x, color) in row.iter_mut().enumerate() {
+            let [r, g, b, alpha] = pixel(x, y);
+            if alpha >= 128 {
+                *color = Color::from_rgb(r, g, b);
+            }
+        }
+    }
+    matrix
+}
+
+#[test]
+fn qoi_matches_reference_encoder() {
+    let mut rgba = Vec::new();
+    for y in 0..H {
+        for x in 0..W {
+            rgba.extend(test_pixel(x, y));
+        }
+    }
+    let data = qoi::encode_to_vec(&rgba, W as u32, H as u32).unwrap();
+
+    let qoi = Qoi::parse(&data).unwrap();
+    assert_eq!(qoi.size(), Size::new(W as u32, H as u32));
+    let mut matrix = [[Color::black(); W]; H];
+    qoi.draw(&mut Canvas::new(&mut matrix), &viewport())
+        .unwrap();
+    assert_eq!(matrix, expected(test_pixel));
+}
+
+#[test]
+fn bmp_24_bit_with_padded_rows() {
+    let stride = (W * 3).div_ceil(4) * 4;
+    assert_ne!(stride, W * 3);
+
+    let mut data = Vec::new();
+    data.extend(b"BM");
+    data.extend(((54 + stride * H) as u32).to_le_bytes());
+    data.extend([0; 4]);
+    data.extend(54u32.to_le_bytes());
+    // BITMAPINFOHEADER, stored bottom to top.
+    data.extend(40u32.to_le_bytes());
+    data.extend((W as i32).to_le_bytes());
+    data.extend((H as i32).to_le_bytes());
+    data.extend(1u16.to_le_bytes());
+    data.extend(24u16.to_le_bytes());
+    data.extend([0; 24]);
+    for y in (0..H).rev() {
+        let start = data.len();
+        for x in 0..W {
+            let [r, g, b, _] = test_pixel(x, y);
+            data.extend([b, g, r]);
+        }
+        data.resize(start + stride, 0xaa);
+    }
+
+    let bmp = Bmp::parse(&data).unwrap();
+    assert_eq!(bmp.size(), Size::new(W as u32, H as u32));
+    let mut matrix = [[Color::black(); W]; H];
+    bmp.draw(&mut Canvas::new(&mut matrix), &viewport())
+        .unwrap();
+    let opaque = |x, y| {
+        let [r, g, b, _] = test_pixel(x, y);
+        [r, g, b, 255]
+    };
+    assert_eq!(matrix, expected(opaque));
+}
+
+// Headers that claim far more pixels than there are, which overflow the size
+// math on a 32 bit chip.
+#[test]
+fn bmp_with_huge_sizes_is_rejected() {
+    let header = |pixels: u32, width: i32, height: i32| {
+        let mut data = Vec::new();
+        data.extend(b"BM");
+        data.extend([0; 8]);
+        data.extend(pixels.to_le_bytes());
+        data.extend(40u32.to_le_bytes());
+        data.extend(width.to_le_bytes());
+        data.extend(height.to_le_bytes());
+        data.extend(1u16.to_le_bytes());
+        data.extend(32u16.to_le_bytes());
+        data.extend([0; 24]);
+        data
+    };
+
+    for (width, height) in [(65536, 65536), (i32::MAX, i32::MAX), (i32::MAX, i32::MIN + 1)] {
+        let data = header(54, width, height);
+        assert!(Bmp::parse(&data).is_err(), "{width}x{height}");
+    }
+    let data = header(u32::MAX, 1, 1);
+    assert!(matches!(Bmp::parse(&data), Err(ImageError::Truncated)));
+}
+
+// A palette of `colors` colors, and which of them each pixel is.
+fn indexed(colors: usize) -> (Vec<u8>, Vec<u8>) {
+    let palette = (0..colors)
+        .flat_map(|i| [(i * 97) as u8, (i * 13 + 50) as u8, (255 - i) as u8])
+        .collect();
+    let pixels = (0..H)
+        .flat_map(|y| (0..W).map(move |x| ((x * 31 + y * 17 + x * y) % colors) as u8))
+        .collect();
+    (palette, pixels)
+}
+
+fn palette_color(palette: &[u8], index: u8) -> [u8; 4] {
+    let entry = &palette[index as usize * 3..][..3];
+    [entry[0], entry[1], entry[2], 255]
+}
+
+// GIF encoders take interlaced images in the order they're stored: every 8th
+// row from 0, every 8th from 4, every 4th from 2, then every other from 1.
+fn interlace(pixels: &[u8]) -> Vec<u8> {
+    [(0, 8), (4, 8), (2, 4), (1, 2)]
+        .into_iter()
+        .flat_map(|(start, step)| (start..H).step_by(step))
+        .flat_map(|y| pixels[y * W..][..W].iter().copied())
+        .collect()
+}
+
+#[test]
+fn interlaced_gifs_match_reference_encoder() {
+    for colors in [2, 16, 256] {
+        let (palette, pixels) = indexed(colors);
+        let mut data = Vec::new();
+        {
+            let mut encoder = gif::Encoder::new(&mut data, W as u16, H as u16, &[]).unwrap();
+            encoder
+                .write_frame(&gif::Frame {
+                    width: W as u16,
+                    height: H as u16,
+                    interlaced: true,
+                    palette: Some(palette.clone()),
+                    buffer: Cow::Owned(interlace(&pixels)),
+                    ..gif::Frame::default()
+                })
+                .unwrap();
+        }
+
+        let gif = Gif::parse(&data).unwrap();
+        assert_eq!(gif.size(), Size::new(W as u32, H as u32));
+        assert_eq!(gif.frames(), 1);
+        let mut player = GifPlayer::<W, H>::new(gif, viewport());
+        assert!(player.update(0).unwrap());
+        let want = expected(|x, y| palette_color(&palette, pixels[y * W + x]));
+        assert_eq!(*player.frame(), want, "{colors} colors");
+    }
+}
+
+// A full frame that's cleared to the background when it's taken down, then a
+// smaller one with see-through pixels, which let the background show.
+#[test]
+fn gif_animation_composites_frames() {
+    let (palette, pixels) = indexed(16);
+    let (left, top, width, height) = (5, 4, 10, 8);
+    let patch: Vec<u8> = (0..width * height)
+        .map(|i| if i % 3 == 0 { 0 } else { 15 })
+        .collect();
+
+    let mut data = Vec::new();
+    {
+        let mut encoder = gif::Encoder::new(&mut data, W as u16, H as u16, &palette).unwrap();
+        encoder.set_repeat(gif::Repeat::Infinite).unwrap();
+        encoder
+            .write_frame(&gif::Frame {
+                width: W as u16,
+                height: H as u16,
+                delay: 10,
+                dispose: gif::DisposalMethod::Background,
+                buffer: Cow::Borrowed(&pixels),
+                ..gif::Frame::default()
+            })
+            .unwrap();
+        encoder
+            .write_frame(&gif::Frame {
+                left: left as u16,
+                top: top as u16,
+                width: width as u16,
+                height: height as u16,
+                delay: 20,
+                transparent: Some(0),
+                buffer: Cow::Borrowed(&patch),
+                ..gif::Frame::default()
+            })
+            .unwrap();
+    }
+
+    let gif = Gif::parse(&data).unwrap();
+    assert_eq!(gif.frames(), 2);
+    assert_eq!(gif.loops(), None);
+    let mut player = GifPlayer::<W, H>::new(gif, viewport());
+    let first = expected(|x, y| palette_color(&palette, pixels[y * W + x]));
+
+    assert!(player.update(0).unwrap());
+    assert_eq!(*player.frame(), first);
+
+    // Delays are in hundredths of a second.
+    assert!(!player.update(99_999).unwrap());
+    assert!(player.update(1).unwrap());
+    let inside =
+        |x: usize, y: usize| (left..left + width).contains(&x) && (top..top + height).contains(&y);
+    let second = expected(|x, y| match inside(x, y) {
+        true if patch[(y - top) * width + x - left] != 0 => palette_color(&palette, 15),
+        _ => [0, 0, 0, 255],
+    });
+    assert_eq!(*player.frame(), second);
+
+    // Then it loops.
+    assert!(player.update(200_000).unwrap());
+    assert_eq!(*player.frame(), first);
+}
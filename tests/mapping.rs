@@ -0,0 +1,89 @@
+// Checks how panel layouts are checked and built.
+
+use board::display::mapping::{bounds, grid, validate, LayoutError, Placement, Rotation};
+
+const PANEL_W: usize = 32;
+const PANEL_H: usize = 16;
+
+#[test]
+fn overlapping_panels_are_rejected() {
+    let layout = [
+        Placement::at(0, 0),
+        Placement::at(32, 0),
+        Placement::at(16, 16),
+        Placement::at(32, 8),
+    ];
+    assert_eq!(
+        validate(&layout, PANEL_W, PANEL_H, 64, 32),
+        Err(LayoutError::Overlap {
+            first: 1,
+            second: 3
+        })
+    );
+
+    // Turned on its side, a panel reaches down into the row below.
+    let layout = [
+        Placement::at(0, 0).rotated(Rotation::Deg90),
+        Placement::at(16, 0),
+        Placement::at(0, 16),
+    ];
+    assert_eq!(
+        validate(&layout, PANEL_W, PANEL_H, 48, 32),
+        Err(LayoutError::Overlap {
+            first: 0,
+            second: 2
+        })
+    );
+}
+
+#[test]
+fn panels_past_the_edge_are_rejected() {
+    let layout = [
+        Placement::at(0, 0),
+        Placement::at(32, 0),
+        Placement::at(33, 16),
+    ];
+    assert_eq!(
+        validate(&layout, PANEL_W, PANEL_H, 64, 32),
+        Err(LayoutError::OutOfBounds { panel: 2 })
+    );
+
+    // Fits the canvas lying down, but not standing up.
+    let layout = [Placement::at(0, 0).rotated(Rotation::Deg270)];
+    assert_eq!(
+        validate(&layout, PANEL_W, PANEL_H, 32, 16),
+        Err(LayoutError::OutOfBounds { panel: 0 })
+    );
+    assert_eq!(validate(&layout, PANEL_W, PANEL_H, 16, 32), Ok(()));
+}
+
+// Two rows of two, where the chain comes back along the second row, right to
+// left and upside down.
+#[test]
+fn serpentine_grid_snakes_back() {
+    let layout = grid::<4>(2, PANEL_W, PANEL_H, true);
+    assert_eq!(
+        layout,
+        [
+            Placement::at(0, 0),
+            Placement::at(32, 0),
+            Placement::at(32, 16).rotated(Rotation::Deg180),
+            Placement::at(0, 16).rotated(Rotation::Deg180),
+        ]
+    );
+    assert_eq!(bounds(&layout, PANEL_W, PANEL_H), (64, 32));
+    assert_eq!(validate(&layout, PANEL_W, PANEL_H, 64, 32), Ok(()));
+
+    // The first pixel of each panel in the chain, which is where the one
+    // before it left off.
+    let firsts = layout.map(|placement| placement.to_virtual(0, 0, PANEL_W, PANEL_H));
+    assert_eq!(firsts, [(0, 0), (32, 0), (63, 31), (31, 31)]);
+    let lasts =
+        layout.map(|placement| placement.to_virtual(PANEL_W - 1, PANEL_H - 1, PANEL_W, PANEL_H));
+    assert_eq!(lasts, [(31, 15), (63, 15), (32, 16), (0, 16)]);
+
+    // Without serpentine, every row runs left to right.
+    let layout = grid::<4>(2, PANEL_W, PANEL_H, false);
+    assert_eq!(layout[2], Placement::at(0, 16));
+    assert_eq!(layout[3], Placement::at(32, 16));
+}
@@ -0,0 +1,35 @@
+use board::{
+    motion::{fixed, Body, Bouncer},
+    sprite::{Sprite, SpriteData},
+};
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    primitives::Rectangle,
+};
+
+const DOT: Sprite = Sprite::new(1, 1, SpriteData::Mask(&[0x80]));
+
+// Updates come in between every refresh, so they're often only a few dozen
+// microseconds apart, which is much less than a fixed point step for slow
+// bodies.
+#[test]
+fn slow_bodies_keep_their_speed_over_short_updates() {
+    let area = Rectangle::new(Point::zero(), Size::new(1000, 1000));
+    let bodies = [
+        Body::new(DOT, Point::new(10, 10), fixed(1) / 4, fixed(1)),
+        Body::new(DOT, Point::new(500, 500), -fixed(1) / 3, -fixed(7)),
+    ];
+    let mut bouncer = Bouncer::new(area, bodies, |_, _| panic!("nothing to bounce off"));
+    for _ in 0..100_000 {
+        bouncer.step(50);
+    }
+
+    // Five seconds.
+    let [slow, backwards] = bouncer.bodies;
+    assert_eq!((slow.x, slow.y), (fixed(10) + fixed(1) / 4 * 5, fixed(15)));
+    assert_eq!(
+        (backwards.x, backwards.y),
+        (fixed(500) - fixed(1) / 3 * 5, fixed(500 - 35))
+    );
+    assert_eq!(slow.position(), Point::new(11, 15));
+}
@@ -0,0 +1,149 @@
+// Runs the display pipeline through the simulator and checks what the panels
+// end up showing.
+
+use std::{env, fs};
+
+use board::{
+    assets,
+    display::{
+        mapping::Placement,
+        sim::{write_png, Line, Simulator},
+        spec::{self, Geometry},
+        Color, Matrix, Modulation,
+    },
+    motion::{fixed, Body, Bouncer},
+    scene::Scene,
+};
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    primitives::Rectangle,
+};
+
+const SINGLE_32X32: Geometry = Geometry::new(32, 32, &[Placement::at(0, 0)]);
+
+fn test_frame<const W: usize, const H: usize>() -> Matrix<W, H> {
+    let mut matrix = [[Color::black(); W]; H];
+    for (y, row) in matrix.iter_mut().enumerate() {
+        for (x, color) in row.iter_mut().enumerate() {
+            *color = Color::from_rgb((x * 8) as u8, (y * 8) as u8, ((x ^ y) * 8) as u8);
+        }
+    }
+    matrix
+}
+
+fn capture<const W: usize, const H: usize>(
+    geometry: Geometry,
+    modulation: Modulation,
+    frame: &Matrix<W, H>,
+) -> (Matrix<W, H>, Vec<usize>) {
+    let mut sim = Simulator::<W, H>::new(geometry, true);
+    sim.set_modulation(modulation);
+    *sim.back_buffer() = *frame;
+    sim.present();
+    let captured = sim.capture();
+    let addresses = sim
+        .pins()
+        .events()
+        .iter()
+        .filter_map(|event| match event.line {
+            Line::Address(address) => Some(address),
+            _ => None,
+        })
+        .collect();
+    (captured, addresses)
+}
+
+// Outdoor panels often light two rows in each half per address, with the
+// shift registers running through both.
+#[test]
+fn eighth_scan_panels_show_the_same_picture() {
+    let geometry = SINGLE_32X32.with_scan(8);
+    geometry.validate(32, 32).unwrap();
+    assert_eq!(geometry.rows_per_address(), 2);
+    assert_eq!(geometry.physical_width(), 64);
+    assert_eq!(geometry.physical_height(), 16);
+    assert_eq!(geometry.physical_to_virtual(0, 0), (0, 0));
+    assert_eq!(geometry.physical_to_virtual(33, 3), (1, 11));
+    assert_eq!(geometry.physical_to_virtual(33, 8), (1, 24));
+
+    let frame = test_frame::<32, 32>();
+    for modulation in [Modulation::Threshold, Modulation::BinaryCoded] {
+        let (expected, _) = capture(SINGLE_32X32, modulation, &frame);
+        let (captured, addresses) = capture(geometry, modulation, &frame);
+        assert_eq!(captured, expected, "{modulation:?}");
+        assert!(addresses.iter().all(|&address| address < 8));
+    }
+}
+
+#[test]
+fn scans_have_to_divide_the_panel() {
+    assert!(SINGLE_32X32.with_scan(8).validate(32, 32).is_ok());
+    assert!(SINGLE_32X32.with_scan(32).validate(32, 32).is_err());
+    assert!(SINGLE_32X32.with_scan(4).validate(32, 32).is_err());
+    const SINGLE_32X48: Geometry = Geometry::new(32, 48, &[Placement::at(0, 0)]);
+    assert!(SINGLE_32X48.with_scan(16).validate(32, 48).is_err());
+}
+
+// Seconds into the DVD demo to capture, side by side in the golden image.
+const GOLDEN_SECONDS: [u32; 4] = [0, 2, 4, 6];
+const GOLDEN_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/dvd.png");
+
+// Runs the DVD demo from main.rs through the simulator, and compares what the
+// panels show against tests/golden/dvd.png. Run with UPDATE_GOLDEN=1 to
+// rewrite it after changing how things look on purpose.
+#[test]
+fn dvd_demo_matches_golden_image() {
+    let area = Rectangle::new(Point::zero(), Size::new(64, 64));
+    let logo = Body::new(
+        assets::DVD_LOGO,
+        Point::new(0, 10),
+        fixed(50) / 3,
+        fixed(50) / 3,
+    )
+    .with_color(Color::from_hsv(0, 255, 255));
+    let mut hue = 0;
+    let mut bouncer = Bouncer::new(area, [logo], |logo, _| {
+        hue = (hue + 137) % 360;
+        logo.color = Color::from_hsv(hue, 255, 255);
+    });
+
+    let mut sim = Simulator::<64, 64>::new(spec::TWO_PANEL_64X64, false);
+    let mut strip = [[Color::black(); 64 * GOLDEN_SECONDS.len()]; 64];
+    let mut elapsed = 0;
+    for (i, seconds) in GOLDEN_SECONDS.into_iter().enumerate() {
+        // Steps about as often as the draw loop on the board does.
+        while elapsed < seconds * 1_000_000 {
+            bouncer.step(20_000);
+            elapsed += 20_000;
+        }
+        bouncer.render(sim.back_buffer());
+        sim.present();
+        let captured = sim.capture();
+        for (row, captured) in strip.iter_mut().zip(captured) {
+            row[i * 64..][..64].copy_from_slice(&captured);
+        }
+    }
+
+    let mut png = Vec::new();
+    write_png(&strip, &mut png).unwrap();
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(GOLDEN_PATH, &png).unwrap();
+        return;
+    }
+    let golden = fs::read(GOLDEN_PATH).expect("no golden image, run with UPDATE_GOLDEN=1");
+    if decode(&png) != decode(&golden) {
+        let actual = env::temp_dir().join("dvd.png");
+        fs::write(&actual, &png).unwrap();
+        panic!("doesn't match {GOLDEN_PATH}, see {}", actual.display());
+    }
+}
+
+// Compares pixels rather than bytes, so it doesn't matter how the PNGs were
+// compressed.
+fn decode(png: &[u8]) -> (u32, u32, Vec<u8>) {
+    let mut reader = png::Decoder::new(png).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    pixels.truncate(info.buffer_size());
+    (info.width, info.height, pixels)
+}
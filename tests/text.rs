@@ -0,0 +1,66 @@
+use board::{
+    display::{Canvas, Color, Matrix},
+    text::{Marquee, MarqueeEnd, TextStyle, FONT_3X5, FONT_6X12},
+};
+use embedded_graphics_core::{
+    geometry::{Point, Size},
+    pixelcolor::{Rgb888, RgbColor},
+    primitives::Rectangle,
+};
+
+// Fonts taller than a byte keep their bottom rows.
+#[test]
+fn tall_fonts_draw_descenders() {
+    let mut matrix = [[Color::black(); 8]; 12];
+    let style = TextStyle::new(FONT_6X12, Rgb888::WHITE);
+    style
+        .draw("g", Point::zero(), &mut Canvas::new(&mut matrix))
+        .unwrap();
+
+    let rows: Vec<String> = matrix
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&color| if color == Color::black() { '.' } else { '#' })
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            "........", "........", "........", "........", "........", ".###....", "#...#...",
+            "#...#...", "#...#...", ".####...", "....#...", ".###....",
+        ]
+    );
+}
+
+fn draw_marquee(marquee: &Marquee<Rgb888, 16>) -> Matrix<20, 5> {
+    let mut matrix = [[Color::black(); 20]; 5];
+    marquee.draw(&mut Canvas::new(&mut matrix)).unwrap();
+    matrix
+}
+
+// Copies of the old text that are already showing have to scroll off before
+// the new text appears, and so does new text that's already showing when it's
+// replaced again.
+#[test]
+fn looping_marquee_text_changes_without_jumping() {
+    let style = TextStyle::new(FONT_3X5, Rgb888::WHITE);
+    let area = Rectangle::new(Point::zero(), Size::new(20, 5));
+    let mut marquee = Marquee::<_, 16>::new(style, area, MarqueeEnd::Loop { gap: 2 }, 1, "AB");
+    // A pixel at a time, with a couple of copies of 9 pixel wide text
+    // showing at once.
+    let texts = ["XY", "CD"];
+    for i in 0..60 {
+        marquee.advance(1_000_000);
+        let before = draw_marquee(&marquee);
+        marquee.set_text(texts[i / 7 % 2]);
+        assert_eq!(draw_marquee(&marquee), before, "after {} pixels", i + 1);
+    }
+
+    // The last text set comes in eventually.
+    for _ in 0..60 {
+        marquee.advance(1_000_000);
+    }
+    assert_eq!(marquee.text(), texts[59 / 7 % 2]);
+}